@@ -0,0 +1,272 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    locale::LocalizedText,
+    player::{controller::PlayerControllerMessage, item::Item},
+    progress::{CollectibleKind, Progress},
+    save::SaveData,
+    ui::toast::{Toast, ToastArea},
+    util::ron::RonLoader,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<AchievementList>()
+        .register_asset_loader(RonLoader::<AchievementList>::new(&["achievements.ron"]))
+        .init_resource::<Achievements>()
+        .add_systems(
+            Update,
+            (PlayEvent::record_controller, Achievements::unlock).chain(),
+        );
+}
+
+/// Something the player did, counted in the save for achievements and stats
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PlayEvent {
+    GroundJump,
+    CoyoteTimeJump,
+    CoyoteFrictionJump,
+    AirJump,
+    Dash,
+    Slide,
+    Slam,
+    WallGrab,
+    Pogo,
+    Death,
+}
+
+impl PlayEvent {
+    fn from_controller(msg: &PlayerControllerMessage) -> Option<Self> {
+        Some(match msg {
+            PlayerControllerMessage::GroundJump => Self::GroundJump,
+            PlayerControllerMessage::CoyoteTimeJump => Self::CoyoteTimeJump,
+            PlayerControllerMessage::CoyoteFrictionJump => Self::CoyoteFrictionJump,
+            PlayerControllerMessage::AirJump => Self::AirJump,
+            PlayerControllerMessage::Dash => Self::Dash,
+            PlayerControllerMessage::Slide => Self::Slide,
+            PlayerControllerMessage::Slam(_) => Self::Slam,
+            PlayerControllerMessage::WallGrab => Self::WallGrab,
+            PlayerControllerMessage::Pogo => Self::Pogo,
+            PlayerControllerMessage::Land => return None,
+        })
+    }
+
+    /// Counts without saving, since these happen all the time. They are written with the next save
+    fn record_controller(
+        mut msg: MessageReader<PlayerControllerMessage>,
+        mut save: ResMut<SaveData>,
+    ) {
+        for event in msg.read().filter_map(Self::from_controller) {
+            save.bypass_change_detection().record(event);
+        }
+    }
+}
+
+/// Every achievement, loaded from `game.achievements.ron`
+///
+/// ```ron
+/// [
+///     (id: "all_tokens", condition: AllTokens),
+///     (id: "no_rocket", condition: All([AllTokens, Never(Dash)]), hidden: true),
+/// ]
+/// ```
+#[derive(Asset, TypePath, Deserialize, Deref, Debug)]
+#[serde(transparent)]
+pub struct AchievementList(Vec<Achievement>);
+
+/// Its name and description are the `achievement.<id>.name` and `achievement.<id>.description` strings
+#[derive(Deserialize, Clone, Debug)]
+pub struct Achievement {
+    pub id: String,
+    pub condition: Condition,
+    /// Hides the name and description until it is unlocked
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl Achievement {
+    pub fn name(&self) -> LocalizedText {
+        LocalizedText::new(format!("achievement.{}.name", self.id))
+    }
+
+    pub fn description(&self) -> LocalizedText {
+        LocalizedText::new(format!("achievement.{}.description", self.id))
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum Condition {
+    /// The event happened at least this many times
+    Count(PlayEvent, u32),
+    /// The event never happened
+    Never(PlayEvent),
+    Item(Item),
+    /// At least this many tokens were collected
+    Tokens(usize),
+    /// Every token in the level was collected
+    AllTokens,
+    /// Played for at least this many seconds
+    PlayTime(f32),
+    /// Still under this many seconds of play time
+    Within(f32),
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    fn met(&self, save: &SaveData, progress: &Progress) -> bool {
+        match self {
+            Self::Count(event, times) => save.count(*event) >= *times,
+            Self::Never(event) => save.count(*event) == 0,
+            Self::Item(item) => save.items.contains(item),
+            Self::Tokens(tokens) => progress.count(CollectibleKind::Token, save).found >= *tokens,
+            Self::AllTokens => {
+                let count = progress.count(CollectibleKind::Token, save);
+                count.total > 0 && count.found == count.total
+            }
+            Self::PlayTime(seconds) => save.play_time >= *seconds,
+            Self::Within(seconds) => save.play_time < *seconds,
+            Self::All(conditions) => conditions.iter().all(|c| c.met(save, progress)),
+            Self::Any(conditions) => conditions.iter().any(|c| c.met(save, progress)),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Achievements {
+    list: Handle<AchievementList>,
+}
+
+impl FromWorld for Achievements {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            list: world.load_asset("game.achievements.ron"),
+        }
+    }
+}
+
+impl Achievements {
+    /// Empty until the list is loaded
+    pub fn list<'a>(&self, lists: &'a Assets<AchievementList>) -> &'a [Achievement] {
+        lists.get(&self.list).map_or(&[], |list| list.as_slice())
+    }
+
+    fn unlock(
+        achievements: Res<Achievements>,
+        lists: Res<Assets<AchievementList>>,
+        progress: Res<Progress>,
+        area: Single<Entity, With<ToastArea>>,
+        mut save: ResMut<SaveData>,
+        mut cmd: Commands,
+    ) {
+        let unlocked = achievements
+            .list(&lists)
+            .iter()
+            .filter(|a| !save.achievements.contains(&a.id))
+            .filter(|a| a.condition.met(&save, &progress))
+            .collect::<Vec<_>>();
+
+        for achievement in unlocked {
+            let title = (
+                LocalizedText::new("achievement.unlocked"),
+                TextFont::from_font_size(16.0),
+            );
+            let name = (achievement.name(), TextFont::from_font_size(24.0));
+
+            cmd.spawn((Toast::bundle(children![title, name]), ChildOf(*area)));
+            save.achievements.insert(achievement.id.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(ron: &str) -> Condition {
+        ron::from_str(ron).unwrap()
+    }
+
+    #[test]
+    fn counts_events() {
+        let mut save = SaveData::default();
+        let progress = Progress::default();
+
+        assert!(!condition("Count(Slam, 2)").met(&save, &progress));
+        assert!(condition("Never(Slam)").met(&save, &progress));
+
+        save.record(PlayEvent::Slam);
+        save.record(PlayEvent::Slam);
+        assert!(condition("Count(Slam, 2)").met(&save, &progress));
+        assert!(!condition("Never(Slam)").met(&save, &progress));
+    }
+
+    #[test]
+    fn items() {
+        let save = SaveData {
+            items: vec![Item::Sword],
+            ..default()
+        };
+
+        assert!(condition("Item(Sword)").met(&save, &Progress::default()));
+        assert!(!condition("Item(Hook)").met(&save, &Progress::default()));
+    }
+
+    #[test]
+    fn tokens() {
+        let mut progress = Progress::default();
+        let ids = (0..3)
+            .map(|i| progress.insert(CollectibleKind::Token, None, vec3(i as f32, 0.0, 0.0)))
+            .collect::<Vec<_>>();
+        let found = |tokens: usize| SaveData {
+            found: ids[..tokens].iter().cloned().collect(),
+            ..default()
+        };
+
+        assert!(condition("Tokens(2)").met(&found(2), &progress));
+        assert!(!condition("Tokens(3)").met(&found(2), &progress));
+        assert!(!condition("AllTokens").met(&found(2), &progress));
+        assert!(condition("AllTokens").met(&found(3), &progress));
+    }
+
+    #[test]
+    fn all_tokens_needs_a_token() {
+        assert!(!condition("AllTokens").met(&SaveData::default(), &Progress::default()));
+    }
+
+    #[test]
+    fn play_time() {
+        let save = SaveData {
+            play_time: 100.0,
+            ..default()
+        };
+        let progress = Progress::default();
+
+        assert!(condition("PlayTime(100.0)").met(&save, &progress));
+        assert!(!condition("PlayTime(101.0)").met(&save, &progress));
+        assert!(condition("Within(101.0)").met(&save, &progress));
+        assert!(!condition("Within(100.0)").met(&save, &progress));
+    }
+
+    #[test]
+    fn combinations() {
+        let save = SaveData {
+            play_time: 10.0,
+            ..default()
+        };
+        let progress = Progress::default();
+
+        assert!(condition("All([Never(Death), Within(20.0)])").met(&save, &progress));
+        assert!(!condition("All([Never(Death), PlayTime(20.0)])").met(&save, &progress));
+        assert!(condition("Any([Count(Death, 1), Within(20.0)])").met(&save, &progress));
+        assert!(!condition("Any([])").met(&save, &progress));
+        assert!(condition("All([])").met(&save, &progress));
+    }
+
+    #[test]
+    fn shipped_list_parses() {
+        let list: AchievementList =
+            ron::from_str(include_str!("../assets/game.achievements.ron")).unwrap();
+        assert!(!list.is_empty());
+    }
+}
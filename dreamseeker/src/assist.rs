@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{player::controller::PlayerControllerSettings, save::SaveData};
+
+const COYOTE_TIME_SCALE: f32 = 3.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Assists>()
+        .add_systems(PreUpdate, Assists::update);
+}
+
+/// Options that make the game easier. Kept in the save
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Assists {
+    pub game_speed: f32,
+    pub infinite_air_jumps: bool,
+    pub infinite_dash: bool,
+    /// Don't count [`DeathTrigger`](crate::trigger::DeathTrigger)s as deaths, only respawn
+    pub invincible: bool,
+    pub long_coyote_time: bool,
+}
+
+impl Default for Assists {
+    fn default() -> Self {
+        Self {
+            game_speed: 1.0,
+            infinite_air_jumps: false,
+            infinite_dash: false,
+            invincible: false,
+            long_coyote_time: false,
+        }
+    }
+}
+
+impl Assists {
+    pub const MIN_GAME_SPEED: f32 = 0.5;
+
+    pub fn active(&self) -> bool {
+        self.game_speed < 1.0
+            || self.infinite_air_jumps
+            || self.infinite_dash
+            || self.invincible
+            || self.long_coyote_time
+    }
+
+    /// Applies the assists on top of the settings given by the player's items
+    pub fn apply(&self, settings: &mut PlayerControllerSettings) {
+        let default = PlayerControllerSettings::default();
+
+        settings.infinite_air_jumps = self.infinite_air_jumps;
+        settings.infinite_dash = self.infinite_dash;
+        settings.coyote_time = if self.long_coyote_time {
+            default.coyote_time * COYOTE_TIME_SCALE
+        } else {
+            default.coyote_time
+        };
+    }
+
+    fn update(assists: Res<Assists>, mut save: ResMut<SaveData>, mut time: ResMut<Time<Virtual>>) {
+        if !assists.is_changed() {
+            return;
+        }
+
+        // Fixed time follows virtual time, so this slows down physics too
+        time.set_relative_speed(assists.game_speed);
+
+        if assists.active() && !save.assisted {
+            save.assisted = true;
+        }
+    }
+}
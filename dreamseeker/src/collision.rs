@@ -8,4 +8,5 @@ pub enum GameLayer {
     Attackable,
     Sword,
     Sensor,
+    Water,
 }
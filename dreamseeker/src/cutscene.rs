@@ -0,0 +1,484 @@
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::{
+    asset::LoadState,
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+};
+use bevy_flurx::{
+    action::{delay, once, wait},
+    prelude::Reactor,
+    task::ReactorTask,
+};
+use serde::Deserialize;
+
+use crate::{
+    GameState,
+    collision::GameLayer,
+    locale::LocalizedText,
+    player::{
+        Player,
+        cinematic::{CameraKeyframe, CameraPath, Cinematic, CinematicPath},
+        controller::PlayerControllerMessage,
+    },
+    ui::screen::{
+        PushScreen, ScreenCommandsExt, achievements::AchievementsScreen, dialogue::DialogueScreen,
+        info::InfoScreen, map::MapScreen, stats::StatsScreen,
+    },
+    util::ron::RonLoader,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Cutscene>()
+        .register_asset_loader(RonLoader::<Cutscene>::new(&["cutscene.ron"]))
+        .add_message::<CutsceneSignal>();
+}
+
+/// A list of steps, loaded from `.cutscene.ron` files. See `cutscenes/example.cutscene.ron`
+///
+/// ```ron
+/// (steps: [
+///     SetState(Cutscene),
+///     CameraPath("gate"),
+///     PlayAnimation(entity: "Gate", clip: "gate.glb#Animation0", wait: true),
+///     PlaySound("chest_open.ogg"),
+///     PushScreen(Info("gate.open")),
+///     Wait(2.0),
+///     PopScreen,
+///     WaitMessage(Player(Land)),
+///     StopCamera,
+///     SetState(InGame),
+/// ])
+/// ```
+#[derive(Asset, TypePath, Deserialize, Clone, Debug)]
+pub struct Cutscene {
+    pub steps: Vec<Step>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum Step {
+    SetState(GameState),
+    /// Waits for a number of seconds
+    Wait(f32),
+    /// Plays a glTF animation on the first [`AnimationPlayer`] under the named entity
+    PlayAnimation {
+        entity: String,
+        clip: String,
+        #[serde(default)]
+        wait: bool,
+    },
+    /// Opens a screen on top of the others
+    PushScreen(CutsceneScreen),
+    /// Closes the screen of the last [`Step::PushScreen`]
+    PopScreen,
+    /// Sends a [`CutsceneSignal`]
+    Signal(String),
+    /// Waits until a message is sent
+    WaitMessage(CutsceneMessage),
+    /// Moves the camera through keyframes of `(time, position, look_at)`
+    Camera {
+        keyframes: Vec<(f32, [f32; 3], [f32; 3])>,
+        #[serde(default)]
+        hold: bool,
+    },
+    /// Plays the [`CinematicPath`] with this id and holds on its last keyframe
+    CameraPath(String),
+    /// Hands the camera back to the player
+    StopCamera,
+    /// Plays a sound by asset path
+    PlaySound(String),
+    /// Despawns the named entity
+    Despawn(String),
+}
+
+/// A screen a cutscene can push
+#[derive(Deserialize, Clone, Debug)]
+pub enum CutsceneScreen {
+    /// An [`InfoScreen`] with the text of a string table key
+    Info(String),
+    /// A [`DialogueScreen`] by asset path
+    Dialogue(String),
+    Map,
+    Stats,
+    Achievements,
+}
+
+/// A message a cutscene can wait for
+#[derive(Deserialize, Clone, Debug)]
+pub enum CutsceneMessage {
+    /// A [`CutsceneSignal`] with this name
+    Signal(String),
+    /// A [`PlayerControllerMessage`] of the same kind, ignoring its values
+    Player(PlayerControllerMessage),
+}
+
+/// The reactor of a cutscene that is playing
+#[derive(Component)]
+pub struct RunningCutscene;
+
+impl RunningCutscene {
+    /// Stops every cutscene and hands the camera back to the player
+    pub fn stop_all(
+        cutscenes: Query<Entity, With<RunningCutscene>>,
+        mut cinematic: ResMut<Cinematic>,
+        mut cmd: Commands,
+    ) {
+        for cutscene in &cutscenes {
+            cmd.entity(cutscene).despawn();
+        }
+        *cinematic = default();
+    }
+}
+
+/// Lets game code and cutscenes wait on each other
+#[derive(Message, Clone, Debug)]
+pub struct CutsceneSignal(pub String);
+
+/// Plays a cutscene when the player enters it
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component, Default)]
+#[require(
+    Transform,
+    Sensor,
+    CollisionEventsEnabled,
+    CollisionLayers::new(GameLayer::Sensor, LayerMask::ALL)
+)]
+#[component(on_add)]
+pub struct CutsceneTrigger {
+    /// Asset path of the cutscene
+    pub cutscene: String,
+    /// Only play the cutscene the first time
+    pub once: bool,
+    #[reflect(ignore)]
+    played: bool,
+}
+
+impl CutsceneTrigger {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        world.commands().entity(ctx.entity).observe(Self::on_enter);
+    }
+
+    fn on_enter(
+        event: On<CollisionStart>,
+        mut q: Query<&mut CutsceneTrigger>,
+        player: Query<&Player>,
+        assets: Res<AssetServer>,
+        mut cmd: Commands,
+    ) -> Result {
+        if !player.contains(event.collider2) {
+            return Ok(());
+        }
+
+        let mut trigger = q.get_mut(event.collider1)?;
+        if trigger.once && trigger.played {
+            return Ok(());
+        }
+        trigger.played = true;
+
+        let cutscene = assets.load(&trigger.cutscene);
+        cmd.spawn((
+            RunningCutscene,
+            Reactor::schedule(move |task| run_cutscene(task, cutscene)),
+        ));
+
+        Ok(())
+    }
+}
+
+pub async fn run_cutscene(task: ReactorTask, cutscene: Handle<Cutscene>) {
+    let steps = task
+        .will(
+            Update,
+            wait::output(
+                move |cutscenes: Res<Assets<Cutscene>>, assets: Res<AssetServer>| {
+                    // Give up instead of waiting forever for a broken file
+                    if let LoadState::Failed(e) = assets.load_state(&cutscene) {
+                        warn!("Skipping the cutscene, it couldn't be loaded: {e}");
+                        return Some(None);
+                    }
+
+                    cutscenes.get(&cutscene).map(|c| Some(c.steps.clone()))
+                },
+            ),
+        )
+        .await;
+
+    let Some(steps) = steps else {
+        return;
+    };
+
+    let mut screen = None;
+    for step in steps {
+        run_step(&task, step, &mut screen).await;
+    }
+}
+
+/// `screen` is the screen of the last [`Step::PushScreen`], for [`Step::PopScreen`] to close
+async fn run_step(task: &ReactorTask, step: Step, screen: &mut Option<Entity>) {
+    match step {
+        Step::SetState(state) => {
+            task.will(
+                Update,
+                once::run(move |mut cmd: Commands| cmd.set_state(state)),
+            )
+            .await;
+        }
+        Step::Wait(seconds) => {
+            task.will(Update, delay::time().with(Duration::from_secs_f32(seconds)))
+                .await;
+        }
+        Step::PlayAnimation {
+            entity,
+            clip,
+            wait: wait_finished,
+        } => {
+            let playing = task
+                .will(Update, once::run(play_animation).with((entity, clip)))
+                .await;
+
+            let Some((entity, node, previous)) = playing else {
+                return;
+            };
+
+            if wait_finished {
+                finish_animation(task.clone(), entity, node, previous).await;
+            } else {
+                task.will(
+                    Update,
+                    once::run(move |mut cmd: Commands| {
+                        let previous = previous.clone();
+                        cmd.spawn(Reactor::schedule(move |task| {
+                            finish_animation(task, entity, node, previous)
+                        }));
+                    }),
+                )
+                .await;
+            }
+        }
+        Step::PushScreen(kind) => {
+            let pushed = task
+                .will(
+                    Update,
+                    once::run(move |assets: Res<AssetServer>, mut cmd: Commands| {
+                        let screen = cmd.spawn_empty().id();
+                        match &kind {
+                            CutsceneScreen::Info(key) => cmd.queue(PushScreen(
+                                screen,
+                                InfoScreen::bundle(LocalizedText::new(key.clone())),
+                            )),
+                            CutsceneScreen::Dialogue(path) => cmd.queue(PushScreen(
+                                screen,
+                                DialogueScreen::bundle(assets.load(path)),
+                            )),
+                            CutsceneScreen::Map => {
+                                cmd.queue(PushScreen(screen, MapScreen::bundle()))
+                            }
+                            CutsceneScreen::Stats => {
+                                cmd.queue(PushScreen(screen, StatsScreen::bundle()))
+                            }
+                            CutsceneScreen::Achievements => {
+                                cmd.queue(PushScreen(screen, AchievementsScreen::bundle()))
+                            }
+                        }
+                        screen
+                    }),
+                )
+                .await;
+            *screen = Some(pushed);
+        }
+        Step::PopScreen => {
+            let Some(screen) = screen.take() else {
+                return;
+            };
+            task.will(
+                Update,
+                once::run(move |mut cmd: Commands| cmd.close_screen(screen)),
+            )
+            .await;
+        }
+        Step::Signal(name) => {
+            task.will(
+                Update,
+                once::run(move |mut msg: MessageWriter<CutsceneSignal>| {
+                    msg.write(CutsceneSignal(name.clone()));
+                }),
+            )
+            .await;
+        }
+        Step::WaitMessage(CutsceneMessage::Signal(name)) => {
+            task.will(
+                Update,
+                wait::until(move |mut msg: MessageReader<CutsceneSignal>| {
+                    msg.read().any(|signal| signal.0 == name)
+                }),
+            )
+            .await;
+        }
+        Step::WaitMessage(CutsceneMessage::Player(kind)) => {
+            let kind = std::mem::discriminant(&kind);
+            task.will(
+                Update,
+                wait::until(move |mut msg: MessageReader<PlayerControllerMessage>| {
+                    msg.read().any(|m| std::mem::discriminant(m) == kind)
+                }),
+            )
+            .await;
+        }
+        Step::Camera { keyframes, hold } => {
+            let path = CameraPath {
+                keyframes: keyframes
+                    .into_iter()
+                    .map(|(time, position, look_at)| CameraKeyframe {
+                        time,
+                        position: Vec3::from_array(position),
+                        look_at: Vec3::from_array(look_at),
+                    })
+                    .collect(),
+                hold,
+            };
+
+            task.will(
+                Update,
+                once::run(move |mut cinematic: ResMut<Cinematic>| cinematic.start(path.clone())),
+            )
+            .await;
+        }
+        Step::CameraPath(id) => {
+            let path = task.will(Update, once::run(CinematicPath::find(id))).await;
+
+            if let Some(path) = path {
+                task.will(
+                    Update,
+                    once::run(move |mut cinematic: ResMut<Cinematic>| {
+                        cinematic.start(CameraPath {
+                            hold: true,
+                            ..path.clone()
+                        });
+                    }),
+                )
+                .await;
+            }
+        }
+        Step::StopCamera => {
+            task.will(
+                Update,
+                once::run(|mut cinematic: ResMut<Cinematic>| cinematic.stop()),
+            )
+            .await;
+        }
+        Step::PlaySound(sound) => {
+            task.will(
+                Update,
+                once::run(move |assets: Res<AssetServer>, mut cmd: Commands| {
+                    cmd.spawn((
+                        AudioPlayer::new(assets.load(&sound)),
+                        PlaybackSettings::DESPAWN,
+                    ));
+                }),
+            )
+            .await;
+        }
+        Step::Despawn(name) => {
+            task.will(
+                Update,
+                once::run(move |q: Query<(Entity, &Name)>, mut cmd: Commands| {
+                    for (entity, _) in q.iter().filter(|(_, n)| n.as_str() == name) {
+                        cmd.entity(entity).despawn();
+                    }
+                }),
+            )
+            .await;
+        }
+    }
+}
+
+/// Returns the animation player and node that started playing, with the graph it had before
+fn play_animation(
+    In((entity, clip)): In<(String, String)>,
+    names: Query<(Entity, &Name)>,
+    children: Query<&Children>,
+    mut aplayer: Query<(&mut AnimationPlayer, Option<&AnimationGraphHandle>)>,
+    assets: Res<AssetServer>,
+    mut graphs: ResMut<Assets<AnimationGraph>>,
+    mut cmd: Commands,
+) -> Option<(Entity, AnimationNodeIndex, Option<AnimationGraphHandle>)> {
+    let (root, _) = names.iter().find(|(_, n)| n.as_str() == entity)?;
+
+    let ap = std::iter::once(root)
+        .chain(children.iter_descendants(root))
+        .find(|&e| aplayer.contains(e))?;
+
+    let (graph, node) = AnimationGraph::from_clip(assets.load(&clip));
+    cmd.entity(ap)
+        .insert(AnimationGraphHandle(graphs.add(graph)));
+
+    let (mut player, previous) = aplayer.get_mut(ap).ok()?;
+    let previous = previous.cloned();
+    player.play(node);
+
+    Some((ap, node, previous))
+}
+
+/// Waits for the animation to end, then gives the player back the graph it had before
+async fn finish_animation(
+    task: ReactorTask,
+    entity: Entity,
+    node: AnimationNodeIndex,
+    previous: Option<AnimationGraphHandle>,
+) {
+    task.will(
+        Update,
+        wait::until(move |aplayer: Query<&AnimationPlayer>| {
+            aplayer
+                .get(entity)
+                .ok()
+                .and_then(|ap| ap.animation(node))
+                .is_none_or(|anim| anim.is_finished())
+        }),
+    )
+    .await;
+
+    task.will(
+        Update,
+        once::run(
+            move |mut aplayer: Query<&mut AnimationPlayer>, mut cmd: Commands| {
+                let Ok(mut ap) = aplayer.get_mut(entity) else {
+                    return;
+                };
+                ap.stop(node);
+
+                match previous.clone() {
+                    Some(graph) => cmd.entity(entity).insert(graph),
+                    None => cmd.entity(entity).remove::<AnimationGraphHandle>(),
+                };
+            },
+        ),
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_cutscene_parses() {
+        let cutscene: Cutscene =
+            ron::from_str(include_str!("../assets/cutscenes/example.cutscene.ron")).unwrap();
+        assert!(!cutscene.steps.is_empty());
+    }
+
+    #[test]
+    fn waits_for_player_messages_by_kind() {
+        let step: Step = ron::from_str("WaitMessage(Player(Slam((0.0, 0.0, 0.0))))").unwrap();
+        let Step::WaitMessage(CutsceneMessage::Player(message)) = step else {
+            panic!("parsed as {step:?}");
+        };
+
+        assert_eq!(
+            std::mem::discriminant(&message),
+            std::mem::discriminant(&PlayerControllerMessage::Slam(Vec3::NEG_Y))
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use avian3d::prelude::*;
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    platform::collections::HashSet,
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    GameState,
+    collision::GameLayer,
+    player::Player,
+    ui::screen::{ScreenCommandsExt, dialogue::DialogueScreen},
+    util::ron::RonLoader,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Dialogue>()
+        .register_asset_loader(RonLoader::<Dialogue>::new(&["dialogue.ron"]))
+        .init_resource::<DialogueFlags>();
+}
+
+/// A graph of dialogue nodes, loaded from `dialogue/<id>.dialogue.ron`
+///
+/// ```ron
+/// (
+///     start: "hello",
+///     nodes: {
+///         "hello": (
+///             speaker: Some("Dreamer"),
+///             pages: ["Oh, a visitor!", "Have you seen my cloud?"],
+///             redirects: [("found_cloud", "thanks")],
+///             choices: [
+///                 (text: "I'll look for it", set_flag: Some("looking")),
+///                 (text: "No", next: Some("sad")),
+///             ],
+///         ),
+///         "sad": (speaker: Some("Dreamer"), pages: ["Oh..."]),
+///         "thanks": (speaker: Some("Dreamer"), pages: ["Thank you!"]),
+///     },
+/// )
+/// ```
+#[derive(Asset, TypePath, Deserialize, Clone, Debug)]
+pub struct Dialogue {
+    pub start: String,
+    pub nodes: HashMap<String, DialogueNode>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct DialogueNode {
+    pub speaker: Option<String>,
+    pub pages: Vec<String>,
+    /// Shown after the last page
+    pub choices: Vec<DialogueChoice>,
+    /// Where to go after the last page when there are no choices
+    pub next: Option<String>,
+    pub set_flags: Vec<String>,
+    /// `(flag, node)` pairs, jumps to the first node whose flag is set
+    pub redirects: Vec<(String, String)>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct DialogueChoice {
+    pub text: String,
+    pub next: Option<String>,
+    pub set_flag: Option<String>,
+    /// Only offer this choice when the flag is set
+    pub requires: Option<String>,
+}
+
+impl Dialogue {
+    /// Returns the node for `id`, following redirects
+    pub fn node(&self, id: &str, flags: &DialogueFlags) -> Option<(String, &DialogueNode)> {
+        let mut id = id.to_string();
+
+        // Bounded, in case redirects form a loop
+        for _ in 0..self.nodes.len() + 1 {
+            let node = self.nodes.get(&id)?;
+            match node
+                .redirects
+                .iter()
+                .find(|(flag, to)| flags.contains(flag) && *to != id)
+            {
+                Some((_, to)) => id = to.clone(),
+                None => return Some((id, node)),
+            }
+        }
+
+        None
+    }
+}
+
+impl DialogueNode {
+    pub fn choices<'a>(
+        &'a self,
+        flags: &'a DialogueFlags,
+    ) -> impl Iterator<Item = &'a DialogueChoice> {
+        self.choices
+            .iter()
+            .filter(|c| c.requires.as_ref().is_none_or(|flag| flags.contains(flag)))
+    }
+}
+
+/// Flags set by dialogue choices, remembered across conversations and kept in the save
+#[derive(Resource, Reflect, Clone, Default, Deref, DerefMut)]
+#[reflect(Resource)]
+pub struct DialogueFlags(pub HashSet<String>);
+
+/// Starts a conversation when the player walks up to it
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component, Default)]
+#[require(
+    Transform,
+    Sensor,
+    CollisionEventsEnabled,
+    CollisionLayers::new(GameLayer::Sensor, LayerMask::ALL),
+    RigidBody::Static,
+    Collider::compound(vec![
+        (
+            vec3(0.0, 1.0, 0.0),
+            Quat::default(),
+            Collider::sphere(2.0),
+        )
+    ]),
+)]
+#[component(on_add)]
+pub struct Npc {
+    /// Id of the dialogue asset
+    pub dialogue: String,
+}
+
+impl Npc {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        let model = world.load_asset("npc.glb#Scene0");
+
+        world
+            .commands()
+            .entity(ctx.entity)
+            .insert(SceneRoot(model))
+            .observe(Self::on_enter);
+    }
+
+    fn on_enter(
+        event: On<CollisionStart>,
+        npc: Query<&Npc>,
+        player: Query<&Player>,
+        state: Res<State<GameState>>,
+        assets: Res<AssetServer>,
+        mut cmd: Commands,
+    ) -> Result {
+        if !player.contains(event.collider2) || *state.get() != GameState::InGame {
+            return Ok(());
+        }
+
+        let npc = npc.get(event.collider1)?;
+        let dialogue = assets.load(format!("dialogue/{}.dialogue.ron", npc.dialogue));
+        cmd.push_screen(DialogueScreen::bundle(dialogue));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dialogue() -> Dialogue {
+        ron::from_str(
+            r#"(
+                start: "hello",
+                nodes: {
+                    "hello": (
+                        pages: ["Hi"],
+                        redirects: [("met", "again"), ("self", "hello")],
+                        choices: [
+                            (text: "Bye"),
+                            (text: "Secret", requires: Some("secret")),
+                        ],
+                    ),
+                    "again": (pages: ["Hi again"], redirects: [("done", "thanks")]),
+                    "thanks": (pages: ["Thanks"]),
+                    "ping": (redirects: [("loop", "pong")]),
+                    "pong": (redirects: [("loop", "ping")]),
+                },
+            )"#,
+        )
+        .unwrap()
+    }
+
+    fn flags(flags: &[&str]) -> DialogueFlags {
+        DialogueFlags(flags.iter().map(|f| f.to_string()).collect())
+    }
+
+    fn node_id(dialogue: &Dialogue, id: &str, flags: &DialogueFlags) -> Option<String> {
+        dialogue.node(id, flags).map(|(id, _)| id)
+    }
+
+    #[test]
+    fn missing_node() {
+        assert_eq!(node_id(&dialogue(), "nowhere", &flags(&[])), None);
+    }
+
+    #[test]
+    fn redirects_need_their_flag() {
+        let dialogue = dialogue();
+
+        assert_eq!(
+            node_id(&dialogue, "hello", &flags(&[])).as_deref(),
+            Some("hello")
+        );
+        assert_eq!(
+            node_id(&dialogue, "hello", &flags(&["met"])).as_deref(),
+            Some("again")
+        );
+    }
+
+    #[test]
+    fn redirects_chain() {
+        let flags = flags(&["met", "done"]);
+        assert_eq!(
+            node_id(&dialogue(), "hello", &flags).as_deref(),
+            Some("thanks")
+        );
+    }
+
+    #[test]
+    fn redirect_to_itself_is_ignored() {
+        let flags = flags(&["self"]);
+        assert_eq!(
+            node_id(&dialogue(), "hello", &flags).as_deref(),
+            Some("hello")
+        );
+    }
+
+    #[test]
+    fn redirect_loop_ends() {
+        assert_eq!(node_id(&dialogue(), "ping", &flags(&["loop"])), None);
+    }
+
+    #[test]
+    fn choices_need_their_flag() {
+        let dialogue = dialogue();
+        let texts = |flags: &DialogueFlags| {
+            dialogue.nodes["hello"]
+                .choices(flags)
+                .map(|c| c.text.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(texts(&flags(&[])), ["Bye"]);
+        assert_eq!(texts(&flags(&["secret"])), ["Bye", "Secret"]);
+    }
+
+    #[test]
+    fn shipped_dialogue_parses() {
+        let dialogue: Dialogue =
+            ron::from_str(include_str!("../assets/dialogue/dreamer.dialogue.ron")).unwrap();
+        assert!(dialogue.nodes.contains_key(&dialogue.start));
+
+        for node in dialogue.nodes.values() {
+            let next = node
+                .next
+                .iter()
+                .chain(node.choices.iter().flat_map(|c| &c.next));
+            for id in next.chain(node.redirects.iter().map(|(_, to)| to)) {
+                assert!(dialogue.nodes.contains_key(id), "missing node {id}");
+            }
+        }
+    }
+}
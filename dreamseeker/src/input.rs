@@ -3,6 +3,7 @@ use bevy_enhanced_input::prelude::InputContextAppExt;
 
 use crate::{
     player::{Player, camera::PlayerCamera},
+    prompt::PromptAppExt,
     ui::Screen,
 };
 
@@ -10,6 +11,23 @@ pub(super) fn plugin(app: &mut App) {
     app.add_input_context_to::<FixedPreUpdate, Player>()
         .add_input_context::<PlayerCamera>()
         .add_input_context::<Screen>();
+
+    app.register_prompt::<camera::CenterCamera>("center_camera")
+        .register_prompt::<camera::Pause>("pause")
+        .register_prompt::<camera::Tp>("teleport")
+        .register_prompt::<camera::MoveCamera>("move_camera")
+        .register_prompt::<camera::Zoom>("zoom")
+        .register_prompt::<player::Walk>("walk")
+        .register_prompt::<player::Jump>("jump")
+        .register_prompt::<player::Move>("move")
+        .register_prompt::<player::Slide>("slide")
+        .register_prompt::<player::Dash>("dash")
+        .register_prompt::<player::WallGrab>("wall_grab")
+        .register_prompt::<player::Attack>("attack")
+        .register_prompt::<player::Grapple>("grapple")
+        .register_prompt::<ui::Confirm>("confirm")
+        .register_prompt::<ui::Move>("navigate")
+        .register_prompt::<ui::NewGame>("new_game");
 }
 
 pub mod camera {
@@ -34,6 +52,10 @@ pub mod camera {
     #[action_output(Vec2)]
     pub struct MoveCamera;
 
+    #[derive(InputAction)]
+    #[action_output(f32)]
+    pub struct Zoom;
+
     pub fn actions() -> impl Bundle {
         actions!(PlayerCamera[
             (
@@ -61,6 +83,13 @@ pub mod camera {
                         .with(DeadZone::default()),
                 )),
             ),
+            (
+                Action::<Zoom>::new(),
+                Bindings::spawn((
+                    Bidirectional::new(KeyCode::KeyZ, KeyCode::KeyX),
+                    Bidirectional::new(GamepadButton::DPadUp, GamepadButton::DPadDown),
+                )),
+            ),
             (
                 Action::<Pause>::new(),
                 bindings![
@@ -114,6 +143,10 @@ pub mod player {
     #[action_output(bool)]
     pub struct Attack;
 
+    #[derive(InputAction)]
+    #[action_output(bool)]
+    pub struct Grapple;
+
     pub fn actions() -> impl Bundle {
         actions!(Player[
             (
@@ -153,6 +186,14 @@ pub mod player {
                     GamepadButton::West,
                 ]
             ),
+            (
+                Action::<Grapple>::new(),
+                bindings![
+                    KeyCode::KeyF,
+                    MouseButton::Middle,
+                    GamepadButton::RightThumb,
+                ]
+            ),
             (
                 Action::<WallGrab>::new(),
                 bindings![
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use bevy::{color::palettes::css::MAGENTA, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{prompt::Prompts, util::ron::RonLoader};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<StringTable>()
+        .register_asset_loader(RonLoader::<StringTable>::new(&["strings.ron"]))
+        .init_resource::<Locale>()
+        .init_resource::<Strings>()
+        .add_systems(
+            Update,
+            (
+                Strings::rebuild,
+                LocalizedText::update,
+                Locale::toggle_debug,
+                Locale::highlight_unlocalized,
+            )
+                .chain(),
+        );
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Self; 2] = [Self::English, Self::Spanish];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Spanish => "es",
+        }
+    }
+
+    /// The language's own name for itself, which is never translated
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Spanish => "Español",
+        }
+    }
+}
+
+/// Translations for one language, loaded from `locale/<code>.strings.ron`
+#[derive(Asset, TypePath, Deserialize, Deref, Debug)]
+#[serde(transparent)]
+pub struct StringTable(HashMap<String, String>);
+
+#[derive(Resource)]
+pub struct Locale {
+    pub language: Language,
+    /// Marks missing keys and text that doesn't go through [`LocalizedText`]
+    pub debug: bool,
+    tables: HashMap<Language, Handle<StringTable>>,
+}
+
+impl FromWorld for Locale {
+    fn from_world(world: &mut World) -> Self {
+        let tables = Language::ALL
+            .into_iter()
+            .map(|l| {
+                (
+                    l,
+                    world.load_asset(format!("locale/{}.strings.ron", l.code())),
+                )
+            })
+            .collect();
+
+        Self {
+            language: default(),
+            debug: false,
+            tables,
+        }
+    }
+}
+
+/// Text that is intentionally not translated, like checkpoint names
+#[derive(Component, Default)]
+pub struct NoLocalize;
+
+#[derive(Component)]
+struct DebugUnlocalized;
+
+/// Text that is neither translated nor marked as intentionally untranslated
+type Unlocalized = (With<Text>, Without<LocalizedText>, Without<NoLocalize>);
+
+impl Locale {
+    fn toggle_debug(mut locale: ResMut<Locale>, keys: Res<ButtonInput<KeyCode>>) {
+        if cfg!(debug_assertions) && keys.just_pressed(KeyCode::F2) {
+            locale.debug = !locale.debug;
+        }
+    }
+
+    fn highlight_unlocalized(
+        locale: Res<Locale>,
+        unlocalized: Query<Entity, Unlocalized>,
+        highlighted: Query<Entity, With<DebugUnlocalized>>,
+        mut cmd: Commands,
+    ) {
+        if !locale.debug {
+            for entity in highlighted {
+                cmd.entity(entity).remove::<(DebugUnlocalized, Outline)>();
+            }
+            return;
+        }
+
+        for entity in unlocalized {
+            if !highlighted.contains(entity) {
+                cmd.entity(entity)
+                    .insert((DebugUnlocalized, Outline::new(px(2), px(0), MAGENTA.into())));
+            }
+        }
+    }
+}
+
+/// The strings of the current language, falling back to English
+#[derive(Resource, Default)]
+pub struct Strings {
+    strings: HashMap<String, String>,
+    debug: bool,
+}
+
+impl Strings {
+    /// Returns the translation of `key`, or the key itself when it is missing
+    pub fn get(&self, key: &str) -> String {
+        match self.strings.get(key) {
+            Some(s) => s.clone(),
+            None if self.debug => format!("⟦{key}⟧"),
+            None => key.to_string(),
+        }
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.strings.contains_key(key)
+    }
+
+    /// Like [`Strings::get`], replacing `{name}` with each argument
+    pub fn format(&self, key: &str, args: &[(String, String)]) -> String {
+        args.iter().fold(self.get(key), |s, (name, value)| {
+            s.replace(&format!("{{{name}}}"), value)
+        })
+    }
+
+    fn rebuild(
+        mut strings: ResMut<Strings>,
+        locale: Res<Locale>,
+        tables: Res<Assets<StringTable>>,
+        mut events: MessageReader<AssetEvent<StringTable>>,
+    ) {
+        if events.read().count() == 0 && !locale.is_changed() {
+            return;
+        }
+
+        let table = |language| {
+            locale
+                .tables
+                .get(&language)
+                .and_then(|handle| tables.get(handle))
+        };
+
+        *strings = Strings::merge(
+            table(Language::English),
+            table(locale.language),
+            locale.debug,
+        );
+    }
+
+    /// The strings of `language`, with English for the ones it is missing
+    pub(crate) fn merge(
+        english: Option<&StringTable>,
+        language: Option<&StringTable>,
+        debug: bool,
+    ) -> Self {
+        let strings = english
+            .into_iter()
+            .chain(language)
+            .flat_map(|table| table.iter())
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        Self { strings, debug }
+    }
+}
+
+/// Keeps the [`Text`] on this entity translated
+#[derive(Component, Clone, Default)]
+#[require(Text)]
+pub struct LocalizedText {
+    pub key: String,
+    pub args: Vec<(String, String)>,
+}
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    pub fn set(&mut self, name: &str, value: impl ToString) {
+        let value = value.to_string();
+        match self.args.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value,
+            None => self.args.push((name.to_string(), value)),
+        }
+    }
+
+    fn update(
+        q: Query<(Ref<LocalizedText>, &mut Text)>,
+        strings: Res<Strings>,
+        prompts: Res<Prompts>,
+    ) {
+        for (localized, mut text) in q {
+            if localized.is_changed() || strings.is_changed() || prompts.is_changed() {
+                text.0 = prompts.fill(strings.format(&localized.key, &localized.args));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(strings: &[(&str, &str)]) -> StringTable {
+        StringTable(
+            strings
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn falls_back_to_english() {
+        let english = table(&[("hello", "Hello"), ("bye", "Bye")]);
+        let spanish = table(&[("hello", "Hola")]);
+        let strings = Strings::merge(Some(&english), Some(&spanish), false);
+
+        assert_eq!(strings.get("hello"), "Hola");
+        assert_eq!(strings.get("bye"), "Bye");
+    }
+
+    #[test]
+    fn missing_tables_fall_back_to_keys() {
+        let english = table(&[("hello", "Hello")]);
+
+        assert_eq!(
+            Strings::merge(Some(&english), None, false).get("hello"),
+            "Hello"
+        );
+        assert_eq!(Strings::merge(None, None, false).get("hello"), "hello");
+    }
+
+    #[test]
+    fn missing_keys_are_marked_in_debug() {
+        let strings = Strings::merge(None, None, true);
+        assert_eq!(strings.get("hello"), "⟦hello⟧");
+    }
+
+    #[test]
+    fn format_fills_arguments() {
+        let english = table(&[("tokens", "{count} of {total}")]);
+        let strings = Strings::merge(Some(&english), None, false);
+        let args = [("count", "3"), ("total", "10")].map(|(k, v)| (k.to_string(), v.to_string()));
+
+        assert_eq!(strings.format("tokens", &args), "3 of 10");
+    }
+}
@@ -9,15 +9,25 @@ use bevy_framepace::FramepacePlugin;
 use bevy_inspector_egui::bevy_egui::EguiPlugin;
 use bevy_skein::SkeinPlugin;
 use dreamseeker_util::{DreamSeekerUtil, construct::Make};
+use serde::Deserialize;
 
 use self::{
     player::camera::PlayerCamera,
-    ui::screen::{ScreenCommandsExt, hud::HudScreen},
+    ui::screen::{ScreenCommandsExt, hud::HudScreen, title::TitleScreen},
 };
 
+mod achievement;
+mod assist;
 mod collision;
+mod cutscene;
+mod dialogue;
 mod input;
+mod locale;
 mod player;
+mod progress;
+mod prompt;
+mod save;
+mod telemetry;
 mod trigger;
 mod ui;
 mod util;
@@ -43,8 +53,19 @@ impl Plugin for DreamSeeker {
             EnhancedInputPlugin,
             PhysicsPlugins::default(),
             DreamSeekerUtil,
+        ))
+        .add_plugins((
+            self::achievement::plugin,
+            self::assist::plugin,
+            self::cutscene::plugin,
+            self::dialogue::plugin,
             self::input::plugin,
+            self::locale::plugin,
             self::player::plugin,
+            self::progress::plugin,
+            self::prompt::plugin,
+            self::save::plugin,
+            self::telemetry::plugin,
             self::trigger::plugin,
             self::ui::plugin,
         ));
@@ -55,7 +76,7 @@ impl Plugin for DreamSeeker {
     }
 }
 
-#[derive(States, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(States, Reflect, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub enum GameState {
     #[default]
     InGame,
@@ -87,6 +108,7 @@ fn setup(mut cmd: Commands, mut cursor: Single<&mut CursorOptions, With<PrimaryW
     cursor.visible = false;
 
     cmd.push_screen(HudScreen::bundle());
+    cmd.push_screen(TitleScreen::bundle());
 }
 
 #[derive(Component)]
@@ -97,8 +119,12 @@ impl MainScene {
         (Self, Name::new("Scene"), Make(Self::make))
     }
 
+    /// The level from Blender, and the entities of `hub.scn.ron` like the world map
     fn make(assets: Res<AssetServer>) -> Result<impl Bundle + use<>> {
-        Ok(SceneRoot(assets.load("hub.glb#Scene0")))
+        Ok((
+            SceneRoot(assets.load("hub.glb#Scene0")),
+            children![DynamicSceneRoot(assets.load("hub.scn.ron"))],
+        ))
     }
 }
 
@@ -2,14 +2,23 @@ use std::f32::consts::PI;
 
 use avian3d::{
     character_controller::move_and_slide::DepenetrationConfig,
-    prelude::{Collider, LinearVelocity, MoveAndSlide, ShapeCastConfig, SpatialQueryFilter},
+    prelude::{
+        Collider, CollisionEnd, CollisionEventsEnabled, CollisionLayers, CollisionStart, LayerMask,
+        LinearVelocity, MoveAndSlide, Sensor, ShapeCastConfig, SpatialQueryFilter,
+    },
 };
 use bevy::{
-    color::palettes::tailwind, input::mouse::MouseWheel, post_process::{
+    color::palettes::tailwind,
+    core_pipeline::prepass::DepthPrepass,
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    input::mouse::MouseWheel,
+    math::VectorSpace,
+    post_process::{
         bloom::Bloom,
         dof::{DepthOfField, DepthOfFieldMode},
         effect_stack::ChromaticAberration,
-    }, prelude::*
+    },
+    prelude::*,
 };
 use bevy_enhanced_input::prelude::*;
 use dreamseeker_util::observers;
@@ -17,19 +26,33 @@ use dreamseeker_util::observers;
 use crate::{
     GameState,
     collision::GameLayer,
-    input::camera::{CenterCamera, MoveCamera, Pause, Tp},
+    input::camera::{CenterCamera, MoveCamera, Pause, Tp, Zoom},
     trigger::CameraNoClip,
-    ui::screen::{ScreenCommandsExt, pause::PauseScreen, teleport::TeleportScreen},
+    ui::screen::{ScreenCommandsExt, map::open_teleport, pause::PauseScreen},
     util::angle::{Angle, AsAngle},
 };
 
-use super::{PLAYER_HEIGHT, Player, controller::PlayerController};
+use super::{
+    PLAYER_HEIGHT, Player,
+    controller::{PlayerController, PlayerState},
+};
 
 const ZOOM_SPEED: f32 = 1.5;
 const MIN_DISTANCE: f32 = 4.0;
 const MAX_DISTANCE: f32 = 8.0;
 const PAN_SPEED: f32 = 90.0;
 const CENTER_SPEED: f32 = 8.0;
+const PITCH: f32 = 26f32.to_radians();
+const MIN_PITCH: f32 = -30f32.to_radians();
+const MAX_PITCH: f32 = 70f32.to_radians();
+const PITCH_SPEED: f32 = 60.0;
+const ZONE_BLEND_SPEED: f32 = 3.0;
+const FLOOR_NORMAL_Y: f32 = 0.7;
+
+const AUTO_FOLLOW_SPEED: f32 = 45.0;
+const AUTO_FOLLOW_DELAY: f32 = 1.5;
+const AUTO_FOLLOW_MIN_SPEED: f32 = 3.0;
+const AUTO_FOLLOW_MAX_ANGLE: f32 = 135f32.to_radians();
 
 const MIN_FOV: f32 = 70f32.to_radians();
 const MAX_FOV: f32 = 100f32.to_radians();
@@ -38,13 +61,19 @@ const FOV_SPEED: f32 = 8.0;
 const PLAYER_SPEED_SLOW: f32 = 5.5;
 const PLAYER_SPEED_FAST: f32 = 9.0;
 
+const WALL_RUN_TILT: f32 = 12f32.to_radians();
+const TILT_SPEED: f32 = 6.0;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
             PlayerCamera::system,
+            PlayerCamera::apply_zones,
+            PlayerCamera::auto_follow,
             PlayerCamera::follow_player,
             PlayerCamera::set_fov,
+            PlayerCamera::tilt,
             PlayerCamera::change_sens,
         )
             .run_if(not(in_state(GameState::Paused))),
@@ -66,6 +95,32 @@ pub struct PlayerCamera {
 
     pub sensitivity: f32,
 
+    /// Rotation around the view direction, in radians
+    pub roll: f32,
+
+    pub pitch: f32,
+    pub min_pitch: f32,
+    pub max_pitch: f32,
+
+    /// Use the vertical axis of [`MoveCamera`] to zoom instead of changing the pitch
+    pub stick_zoom: bool,
+
+    /// Slowly turn the camera behind the player while running
+    pub auto_follow: bool,
+    /// In degrees per second
+    pub auto_follow_speed: f32,
+    /// Time left before auto follow resumes after moving the camera manually
+    pub auto_follow_delay: f32,
+
+    /// Camera zones the player is inside, the last one entered takes effect
+    pub zones: Vec<Entity>,
+    pub yaw_locked: bool,
+    pub zone_distance: ZoneOverride<f32>,
+    pub zone_pitch: ZoneOverride<f32>,
+    /// Pitch limits of the current zone, replacing `min_pitch` and `max_pitch`
+    pub zone_pitch_limits: Option<(f32, f32)>,
+    pub zone_look_at: ZoneOverride<Vec3>,
+
     #[reflect(ignore)]
     pub collider: Collider,
 }
@@ -85,12 +140,41 @@ impl Default for PlayerCamera {
 
             sensitivity: 1.0,
 
+            roll: 0.0,
+
+            pitch: PITCH,
+            min_pitch: MIN_PITCH,
+            max_pitch: MAX_PITCH,
+
+            stick_zoom: false,
+
+            auto_follow: false,
+            auto_follow_speed: AUTO_FOLLOW_SPEED,
+            auto_follow_delay: 0.0,
+
+            zones: Vec::new(),
+            yaw_locked: false,
+            zone_distance: default(),
+            zone_pitch: default(),
+            zone_pitch_limits: None,
+            zone_look_at: default(),
+
             collider: Collider::sphere(0.25),
         }
     }
 }
 
 impl PlayerCamera {
+    /// Forgets everything about the current level, keeping the player's preferences
+    pub fn reset(&mut self) {
+        *self = Self {
+            sensitivity: self.sensitivity,
+            stick_zoom: self.stick_zoom,
+            auto_follow: self.auto_follow,
+            ..default()
+        };
+    }
+
     pub fn bundle() -> impl Bundle {
         (
             Self::default(),
@@ -111,6 +195,8 @@ impl PlayerCamera {
                 },
                 ..default()
             },
+            // Needed for the player's shadow decal
+            DepthPrepass,
             Bloom::NATURAL,
             DepthOfField {
                 aperture_f_stops: 0.15,
@@ -122,12 +208,19 @@ impl PlayerCamera {
                 intensity: 0.05,
                 ..default()
             },
-            observers![Self::on_center, Self::on_move, Self::on_pause, Self::on_tp],
+            observers![
+                Self::on_center,
+                Self::on_move,
+                Self::on_zoom,
+                Self::on_pause,
+                Self::on_tp
+            ],
         )
     }
 
     fn offset(&self) -> Vec3 {
-        let vert = Quat::from_axis_angle(Vec3::X, 26f32.to_radians());
+        let pitch = self.zone_pitch.apply(self.pitch);
+        let vert = Quat::from_axis_angle(Vec3::X, pitch);
         let rot = Quat::from_axis_angle(Vec3::Y, self.visual_rotation.get()) * vert;
         rot * (Vec3::NEG_Z * self.distance)
     }
@@ -137,16 +230,35 @@ impl PlayerCamera {
             return;
         }
 
-        let zoom_step = ZOOM_SPEED * -cstick.y * dt;
         let pan_step = PAN_SPEED.to_radians() * cstick.x * self.sensitivity * dt;
 
+        if self.stick_zoom {
+            self.change_zoom(cstick.y, dt);
+        } else {
+            let pitch_step = PITCH_SPEED.to_radians() * -cstick.y * self.sensitivity * dt;
+            let (min, max) = self.pitch_limits();
+            self.pitch = (self.pitch + pitch_step).clamp(min, max);
+        }
+
+        if !self.yaw_locked {
+            self.rotation += pan_step.as_angle();
+            self.visual_rotation = self.rotation;
+        }
+    }
+
+    fn pitch_limits(&self) -> (f32, f32) {
+        self.zone_pitch_limits
+            .unwrap_or((self.min_pitch, self.max_pitch))
+    }
+
+    fn change_zoom(&mut self, amount: f32, dt: f32) {
+        let zoom_step = ZOOM_SPEED * -amount * dt;
         self.zoom = (self.zoom + zoom_step).clamp(0.0, 1.0);
+    }
 
+    fn zoom_distance(&self) -> f32 {
         let distance_interval = MAX_DISTANCE - MIN_DISTANCE;
-        self.distance = MIN_DISTANCE + distance_interval * self.zoom;
-
-        self.rotation += pan_step.as_angle();
-        self.visual_rotation = self.rotation;
+        MIN_DISTANCE + distance_interval * self.zoom
     }
 
     fn center(&mut self, facing: Angle) {
@@ -191,7 +303,10 @@ impl PlayerCamera {
         mut camera: Single<&mut PlayerCamera>,
         pc: Single<&PlayerController>,
     ) {
-        camera.center(pc.facing);
+        if !camera.yaw_locked {
+            camera.center(pc.facing);
+        }
+        camera.auto_follow_delay = AUTO_FOLLOW_DELAY;
     }
 
     fn on_move(
@@ -200,6 +315,18 @@ impl PlayerCamera {
         time: Res<Time>,
     ) {
         camera.apply(event.value, time.delta_secs());
+
+        if event.value != Vec2::ZERO {
+            camera.auto_follow_delay = AUTO_FOLLOW_DELAY;
+        }
+    }
+
+    fn on_zoom(event: On<Fire<Zoom>>, mut camera: Single<&mut PlayerCamera>, time: Res<Time>) {
+        camera.change_zoom(event.value, time.delta_secs());
+
+        if event.value != 0.0 {
+            camera.auto_follow_delay = AUTO_FOLLOW_DELAY;
+        }
     }
 
     fn on_pause(_: On<Start<Pause>>, mut cmd: Commands, state: Res<State<GameState>>) {
@@ -212,7 +339,7 @@ impl PlayerCamera {
 
     fn on_tp(_: On<Start<Tp>>, mut cmd: Commands, state: Res<State<GameState>>) {
         if state.get() == &GameState::InGame {
-            cmd.push_screen(TeleportScreen::bundle());
+            cmd.run_system_cached(open_teleport);
         }
     }
 
@@ -220,7 +347,94 @@ impl PlayerCamera {
         camera.update(time.delta_secs());
     }
 
-    fn follow_player(
+    fn apply_zones(
+        mut camera: Single<&mut PlayerCamera>,
+        zones: Query<&CameraZone>,
+        time: Res<Time>,
+    ) {
+        let zone = camera.zones.last().and_then(|&e| zones.get(e).ok());
+        let t = 1.0 - f32::exp(-ZONE_BLEND_SPEED * time.delta_secs());
+
+        camera
+            .zone_distance
+            .update(zone.and_then(|z| z.distance), t);
+        camera
+            .zone_pitch
+            .update(zone.and_then(|z| z.pitch).map(f32::to_radians), t);
+        camera.zone_look_at.update(zone.and_then(|z| z.look_at), t);
+
+        let (min, max) = (camera.min_pitch, camera.max_pitch);
+        camera.zone_pitch_limits = zone
+            .filter(|z| z.min_pitch.is_some() || z.max_pitch.is_some())
+            .map(|z| {
+                (
+                    z.min_pitch.map_or(min, f32::to_radians),
+                    z.max_pitch.map_or(max, f32::to_radians),
+                )
+            });
+        // Ease into tighter limits instead of snapping
+        let (min, max) = camera.pitch_limits();
+        let pitch = camera.pitch;
+        camera.pitch += (pitch.clamp(min, max) - pitch) * t;
+
+        let zoom_distance = camera.zoom_distance();
+        camera.distance = camera.zone_distance.apply(zoom_distance);
+
+        let yaw = zone
+            .and_then(|z| z.yaw)
+            .map(|yaw| yaw.to_radians().as_angle());
+        if let Some(yaw) = yaw
+            && camera.rotation != yaw
+        {
+            camera.center(yaw);
+        }
+        camera.yaw_locked = yaw.is_some();
+    }
+
+    fn auto_follow(
+        mut camera: Single<&mut PlayerCamera>,
+        player: Single<(&PlayerState, &LinearVelocity), With<Player>>,
+        zones: Query<&CameraZone>,
+        time: Res<Time>,
+    ) {
+        let dt = time.delta_secs();
+        camera.auto_follow_delay = (camera.auto_follow_delay - dt).max(0.0);
+
+        if !camera.auto_follow
+            || camera.yaw_locked
+            || camera.auto_follow_delay > 0.0
+            || camera.visual_rotation != camera.rotation
+        {
+            return;
+        }
+
+        let (state, velocity) = *player;
+        if !matches!(state, PlayerState::Grounded(_))
+            || velocity.xz().length() < AUTO_FOLLOW_MIN_SPEED
+        {
+            return;
+        }
+
+        let speed = camera
+            .zones
+            .last()
+            .and_then(|&e| zones.get(e).ok())
+            .and_then(|z| z.auto_follow)
+            .unwrap_or(camera.auto_follow_speed);
+
+        // Don't swing around when running towards the camera
+        let target = vec2(velocity.z, velocity.x).to_angle().as_angle();
+        let diff = target.diff(camera.rotation);
+        if diff.abs() > AUTO_FOLLOW_MAX_ANGLE {
+            return;
+        }
+
+        let max_step = speed.to_radians() * dt;
+        camera.rotation += diff.clamp(-max_step, max_step).as_angle();
+        camera.visual_rotation = camera.rotation;
+    }
+
+    pub(super) fn follow_player(
         mut player_pos: Local<Vec3>,
         mut camera: Single<(&mut Transform, &PlayerCamera), Without<Player>>,
         player: Single<(&Transform, &Collider, Entity), With<Player>>,
@@ -271,15 +485,40 @@ impl PlayerCamera {
             &filter,
         );
 
+        let camera_dir = camera_offset.normalize_or_zero();
+
         match hit {
             None => camera.0.translation = *player_pos + camera_offset + offset,
+            // When looking up from below the player, slide along the floor instead of
+            // pulling the camera into the player
+            Some(hit) if hit.normal1.y > FLOOR_NORMAL_Y => {
+                let start = *player_pos + camera_dir * hit.distance + offset;
+                let slide = (camera_offset - camera_dir * hit.distance).reject_from(hit.normal1);
+
+                let slide_hit = mas.spatial_query.cast_shape(
+                    &camera.1.collider,
+                    start,
+                    Quat::default(),
+                    Dir3::new(slide).unwrap_or(Dir3::Z),
+                    &ShapeCastConfig::from_max_distance(slide.length()),
+                    &filter,
+                );
+
+                camera.0.translation = match slide_hit {
+                    None => start + slide,
+                    Some(slide_hit) => start + slide.normalize_or_zero() * slide_hit.distance,
+                };
+            }
             Some(hit) => {
-                camera.0.translation =
-                    *player_pos + camera_offset.normalize_or_zero() * hit.distance + offset;
+                camera.0.translation = *player_pos + camera_dir * hit.distance + offset;
             }
         }
 
-        camera.0.look_at(*player_pos, Dir3::Y);
+        let look_at = camera.1.zone_look_at.apply(*player_pos);
+        camera.0.look_at(look_at, Dir3::Y);
+
+        let roll = camera.1.roll;
+        camera.0.rotate_local_z(roll);
     }
 
     fn set_fov(
@@ -303,10 +542,24 @@ impl PlayerCamera {
         proj.fov = *fov;
     }
 
-    fn change_sens(
-        mut msgs: MessageReader<MouseWheel>,
-        mut camera: Single<&mut PlayerCamera>,
+    fn tilt(
+        mut camera: Single<(&mut PlayerCamera, &Transform)>,
+        player: Single<&PlayerState>,
+        time: Res<Time>,
     ) {
+        // Tilt away from the wall while wall running
+        let target = match *player {
+            PlayerState::WallRun(wstate) => {
+                camera.1.right().dot(*wstate.wall_normal()) * WALL_RUN_TILT
+            }
+            _ => 0.0,
+        };
+
+        let roll = camera.0.roll;
+        camera.0.roll += (target - roll) * (1.0 - f32::exp(-TILT_SPEED * time.delta_secs()));
+    }
+
+    fn change_sens(mut msgs: MessageReader<MouseWheel>, mut camera: Single<&mut PlayerCamera>) {
         for msg in msgs.read() {
             camera.sensitivity += msg.y;
             if camera.sensitivity < 0.5 {
@@ -318,3 +571,83 @@ impl PlayerCamera {
         }
     }
 }
+
+/// A value a [`CameraZone`] blends towards, kept after leaving so it can blend back out
+#[derive(Reflect, Clone, Copy, Default)]
+pub struct ZoneOverride<T> {
+    pub value: T,
+    pub weight: f32,
+}
+
+impl<T: Copy> ZoneOverride<T> {
+    fn update(&mut self, value: Option<T>, t: f32) {
+        if let Some(value) = value {
+            self.value = value;
+        }
+
+        let weight = if value.is_some() { 1.0 } else { 0.0 };
+        self.weight += (weight - self.weight) * t;
+    }
+}
+
+impl<T: VectorSpace<Scalar = f32>> ZoneOverride<T> {
+    fn apply(&self, base: T) -> T {
+        base.lerp(self.value, self.weight)
+    }
+}
+
+/// Overrides the framing of the player camera while the player is inside it
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component, Default)]
+#[require(
+    Transform,
+    Sensor,
+    CollisionEventsEnabled,
+    CollisionLayers::new(GameLayer::Sensor, LayerMask::ALL)
+)]
+#[component(on_add)]
+pub struct CameraZone {
+    pub distance: Option<f32>,
+    /// In degrees
+    pub pitch: Option<f32>,
+    /// Lowest pitch the player can look from, in degrees
+    pub min_pitch: Option<f32>,
+    /// Highest pitch the player can look from, in degrees
+    pub max_pitch: Option<f32>,
+    /// In degrees, locks the camera's rotation
+    pub yaw: Option<f32>,
+    pub look_at: Option<Vec3>,
+    /// Auto follow speed in degrees per second, `0` disables it
+    pub auto_follow: Option<f32>,
+}
+
+impl CameraZone {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        world
+            .commands()
+            .entity(ctx.entity)
+            .observe(Self::on_enter)
+            .observe(Self::on_exit);
+    }
+
+    fn on_enter(
+        event: On<CollisionStart>,
+        player: Query<&Player>,
+        mut camera: Single<&mut PlayerCamera>,
+    ) {
+        if player.contains(event.collider2) {
+            camera.zones.retain(|&e| e != event.collider1);
+            camera.zones.push(event.collider1);
+        }
+    }
+
+    fn on_exit(
+        event: On<CollisionEnd>,
+        player: Query<&Player>,
+        mut camera: Single<&mut PlayerCamera>,
+    ) {
+        if player.contains(event.collider2) {
+            camera.zones.retain(|&e| e != event.collider1);
+        }
+    }
+}
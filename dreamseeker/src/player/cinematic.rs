@@ -0,0 +1,358 @@
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+};
+use bevy_flurx::{
+    action::{delay, once, wait},
+    prelude::Reactor,
+    task::ReactorTask,
+};
+
+use crate::GameState;
+
+use super::camera::PlayerCamera;
+
+const BLEND_SPEED: f32 = 4.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Cinematic>().add_systems(
+        Update,
+        Cinematic::update
+            .after(PlayerCamera::follow_player)
+            .run_if(not(in_state(GameState::Paused))),
+    );
+}
+
+#[derive(Reflect, Clone, Copy, Debug)]
+pub struct CameraKeyframe {
+    /// Seconds from the start of the path
+    pub time: f32,
+    pub position: Vec3,
+    pub look_at: Vec3,
+}
+
+#[derive(Reflect, Clone, Default, Debug)]
+pub struct CameraPath {
+    pub keyframes: Vec<CameraKeyframe>,
+    /// Stay on the last keyframe until [`Cinematic::stop`] runs
+    pub hold: bool,
+}
+
+impl CameraPath {
+    /// Slowly circles around `center`, starting on the side of `from`
+    pub fn orbit(center: Vec3, from: Vec3, distance: f32, height: f32, duration: f32) -> Self {
+        const STEPS: usize = 4;
+        const SWEEP: f32 = 40f32.to_radians();
+
+        let dir = (from - center).xz().normalize_or(Vec2::Y);
+        let start = dir.to_angle();
+
+        let keyframes = (0..=STEPS)
+            .map(|i| {
+                let t = i as f32 / STEPS as f32;
+                let offset = Vec2::from_angle(start + SWEEP * t) * distance;
+
+                CameraKeyframe {
+                    time: duration * t,
+                    position: center + vec3(offset.x, height, offset.y),
+                    look_at: center,
+                }
+            })
+            .collect();
+
+        Self {
+            keyframes,
+            hold: false,
+        }
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |k| k.time)
+    }
+
+    /// Returns the camera position and look at point at `time`, using Catmull-Rom
+    /// between keyframes
+    fn sample(&self, time: f32) -> Option<(Vec3, Vec3)> {
+        let keys = &self.keyframes;
+        let first = keys.first()?;
+        let last = keys.last()?;
+
+        if time <= first.time {
+            return Some((first.position, first.look_at));
+        }
+        if time >= last.time {
+            return Some((last.position, last.look_at));
+        }
+
+        let i = keys.iter().rposition(|k| k.time <= time)?;
+        let k1 = keys[i];
+        let k2 = keys[(i + 1).min(keys.len() - 1)];
+        let k0 = keys[i.saturating_sub(1)];
+        let k3 = keys[(i + 2).min(keys.len() - 1)];
+
+        let span = k2.time - k1.time;
+        let t = if span > 0.0 {
+            (time - k1.time) / span
+        } else {
+            1.0
+        };
+
+        Some((
+            catmull_rom(k0.position, k1.position, k2.position, k3.position, t),
+            catmull_rom(k0.look_at, k1.look_at, k2.look_at, k3.look_at, t),
+        ))
+    }
+}
+
+fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+/// Takes over the player camera while a [`CameraPath`] plays, then blends back
+#[derive(Resource, Default)]
+pub struct Cinematic {
+    path: Option<CameraPath>,
+    time: f32,
+    weight: f32,
+    last: Option<Transform>,
+}
+
+impl Cinematic {
+    pub fn start(&mut self, path: CameraPath) {
+        self.path = Some(path);
+        self.time = 0.0;
+    }
+
+    /// Hands the camera back to the player
+    pub fn stop(&mut self) {
+        self.path = None;
+    }
+
+    /// Whether the current path reached its end, for use with `wait::until`
+    pub fn finished(cinematic: Res<Cinematic>) -> bool {
+        cinematic
+            .path
+            .as_ref()
+            .is_none_or(|path| cinematic.time >= path.duration())
+    }
+
+    fn update(
+        mut cinematic: ResMut<Cinematic>,
+        mut camera: Single<&mut Transform, With<PlayerCamera>>,
+        time: Res<Time>,
+    ) {
+        let dt = time.delta_secs();
+        cinematic.time += dt;
+
+        let sample = cinematic
+            .path
+            .as_ref()
+            .and_then(|path| path.sample(cinematic.time));
+
+        if let Some((position, look_at)) = sample {
+            cinematic.last =
+                Some(Transform::from_translation(position).looking_at(look_at, Dir3::Y));
+        }
+
+        if let Some(path) = &cinematic.path
+            && !path.hold
+            && cinematic.time >= path.duration()
+        {
+            cinematic.path = None;
+        }
+
+        let target = if cinematic.path.is_some() { 1.0 } else { 0.0 };
+        cinematic.weight += (target - cinematic.weight) * (1.0 - f32::exp(-BLEND_SPEED * dt));
+
+        // Blend from wherever the player camera would be
+        if let Some(last) = cinematic.last
+            && cinematic.weight > 0.001
+        {
+            let weight = cinematic.weight;
+            camera.translation = camera.translation.lerp(last.translation, weight);
+            camera.rotation = camera.rotation.slerp(last.rotation, weight);
+        }
+    }
+}
+
+/// Plays `path` and waits until it reaches its end
+pub async fn play_path(task: &ReactorTask, path: CameraPath) {
+    task.will(
+        Update,
+        once::run(move |mut cinematic: ResMut<Cinematic>| cinematic.start(path.clone())),
+    )
+    .await;
+    task.will(Update, wait::until(Cinematic::finished)).await;
+}
+
+/// A camera path authored as an empty, with [`CinematicKeyframe`] empties as children.
+/// Each keyframe looks along its own forward direction
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component, Default)]
+#[require(Transform)]
+#[component(on_add)]
+pub struct CinematicPath {
+    pub id: String,
+    /// Play this path when the level loads
+    pub intro: bool,
+}
+
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component, Default)]
+#[require(Transform)]
+pub struct CinematicKeyframe(pub f32);
+
+type Keyframes<'w, 's> = Query<'w, 's, (&'static CinematicKeyframe, &'static GlobalTransform)>;
+
+impl CinematicPath {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        if !world.get::<Self>(ctx.entity).unwrap().intro {
+            return;
+        }
+
+        let entity = ctx.entity;
+        world
+            .commands()
+            .spawn(Reactor::schedule(move |task| intro(task, entity)));
+    }
+
+    /// Builds the path with the given id, for use with `once::run`
+    pub fn find(
+        id: impl Into<String>,
+    ) -> impl FnMut(Query<(&CinematicPath, &Children)>, Keyframes) -> Option<CameraPath> {
+        let id = id.into();
+        move |paths, keyframes| {
+            let (_, children) = paths.iter().find(|(path, _)| path.id == id)?;
+            Some(Self::build(children, &keyframes))
+        }
+    }
+
+    fn build(children: &Children, keyframes: &Keyframes) -> CameraPath {
+        let mut keyframes = keyframes
+            .iter_many(children)
+            .map(|(key, transform)| CameraKeyframe {
+                time: key.0,
+                position: transform.translation(),
+                look_at: transform.translation() + *transform.forward(),
+            })
+            .collect::<Vec<_>>();
+
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        CameraPath {
+            keyframes,
+            hold: false,
+        }
+    }
+}
+
+async fn intro(task: ReactorTask, entity: Entity) {
+    // Wait for the keyframes to spawn and their transforms to propagate
+    task.will(Update, delay::frames().with(2)).await;
+
+    let path = task
+        .will(
+            Update,
+            once::run(
+                move |paths: Query<&Children, With<CinematicPath>>, keyframes: Keyframes| {
+                    paths
+                        .get(entity)
+                        .ok()
+                        .map(|children| CinematicPath::build(children, &keyframes))
+                },
+            ),
+        )
+        .await;
+
+    let Some(path) = path else {
+        return;
+    };
+
+    task.will(
+        Update,
+        once::run(|mut cmd: Commands| cmd.set_state(GameState::Cutscene)),
+    )
+    .await;
+
+    play_path(&task, path).await;
+
+    task.will(
+        Update,
+        once::run(|mut cmd: Commands| cmd.set_state(GameState::InGame)),
+    )
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(points: &[(f32, f32)]) -> CameraPath {
+        CameraPath {
+            keyframes: points
+                .iter()
+                .map(|&(time, x)| CameraKeyframe {
+                    time,
+                    position: vec3(x, 0.0, 0.0),
+                    look_at: vec3(0.0, x, 0.0),
+                })
+                .collect(),
+            hold: false,
+        }
+    }
+
+    #[test]
+    fn empty_path_has_no_sample() {
+        assert_eq!(path(&[]).sample(0.0), None);
+    }
+
+    #[test]
+    fn clamps_to_the_ends() {
+        let path = path(&[(1.0, 2.0), (2.0, 5.0)]);
+
+        assert_eq!(
+            path.sample(0.0),
+            Some((vec3(2.0, 0.0, 0.0), vec3(0.0, 2.0, 0.0)))
+        );
+        assert_eq!(
+            path.sample(3.0),
+            Some((vec3(5.0, 0.0, 0.0), vec3(0.0, 5.0, 0.0)))
+        );
+    }
+
+    #[test]
+    fn passes_through_keyframes() {
+        let path = path(&[(0.0, 0.0), (1.0, 4.0), (2.0, -1.0), (4.0, 3.0)]);
+
+        for key in &path.keyframes {
+            let (position, look_at) = path.sample(key.time).unwrap();
+            assert!(position.abs_diff_eq(key.position, 1e-5));
+            assert!(look_at.abs_diff_eq(key.look_at, 1e-5));
+        }
+    }
+
+    #[test]
+    fn evenly_spaced_points_are_linear() {
+        let path = path(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]);
+
+        // The end segments repeat their end keyframe, so only the inner one is a line
+        for time in [1.0, 1.25, 1.5, 1.75, 2.0] {
+            let (position, _) = path.sample(time).unwrap();
+            assert!((position.x - time).abs() < 1e-5, "{time}: {position}");
+        }
+    }
+
+    #[test]
+    fn keyframes_at_the_same_time_dont_divide_by_zero() {
+        let path = path(&[(0.0, 0.0), (1.0, 1.0), (1.0, 5.0), (2.0, 6.0)]);
+
+        let (position, look_at) = path.sample(1.0).unwrap();
+        assert!(position.is_finite() && look_at.is_finite());
+    }
+}
@@ -3,16 +3,18 @@ use std::{f32::consts::PI, time::Duration};
 use avian3d::{character_controller::move_and_slide::DepenetrationConfig, prelude::*};
 use bevy::{ecs::query::QueryData, prelude::*};
 use bevy_enhanced_input::prelude::*;
+use serde::Deserialize;
 
 use crate::{
     GameState,
     collision::GameLayer,
-    input::player::{Dash, Jump, Move, Slide, Walk, WallGrab},
+    input::player::{Dash, Grapple, Jump, Move, Slide, Walk, WallGrab},
     player::{PLAYER_HEIGHT, PLAYER_WIDTH},
+    trigger::Water,
     util::angle::Angle,
 };
 
-use super::{PlayerModel, camera::PlayerCamera};
+use super::{PlayerModel, camera::PlayerCamera, grapple::GrappleAnchor};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_message::<PlayerControllerMessage>()
@@ -49,8 +51,12 @@ pub struct PlayerControllerSettings {
 
     pub air_jumps: u8,
     pub air_jump_forward_boost: f32,
+    /// Assist, ignores `air_jumps`
+    pub infinite_air_jumps: bool,
 
     pub dash_enabled: bool,
+    /// Assist, dashing doesn't need to be refreshed
+    pub infinite_dash: bool,
     pub dash_velocity: f32,
     pub dash_height: f32,
 
@@ -66,6 +72,7 @@ pub struct PlayerControllerSettings {
     pub slam_pause: f32,
     pub slam_velocity: f32,
     pub slam_jump_boost: f32,
+    pub slam_shockwave_radius: f32,
 
     pub wall_grab_enabled: bool,
     pub wall_grab_min_normal: f32,
@@ -77,7 +84,46 @@ pub struct PlayerControllerSettings {
     pub wall_jump_max_vertical: f32,
     pub wall_jump_add_horizontal: f32,
 
+    pub wall_run_enabled: bool,
+    pub wall_run_min_speed: f32,
+    pub wall_run_time: f32,
+    pub wall_run_gravity_scale: f32,
+
+    pub grapple_enabled: bool,
+    pub grapple_range: f32,
+    /// Cosine of the largest angle between the camera direction and an anchor that can be grappled
+    pub grapple_aim_cos: f32,
+    pub grapple_min_length: f32,
+    pub grapple_reel_speed: f32,
+
+    pub ledge_grab_enabled: bool,
+    /// How far in front of the player to look for a ledge
+    pub ledge_grab_reach: f32,
+    /// How far above the player's head a ledge can be grabbed
+    pub ledge_grab_height: f32,
+    /// How far below the player's head a ledge can be grabbed
+    pub ledge_grab_depth: f32,
+    pub ledge_climb_time: f32,
+    /// Time after letting go of a ledge before another one can be grabbed
+    pub ledge_regrab_cooldown: f32,
+
     pub min_sword_bounce: f32,
+
+    pub swim_speed: f32,
+    pub swim_accel: f32,
+    pub swim_gravity_scale: f32,
+    pub swim_buoyancy: f32,
+    /// How far below the surface the center of the player floats
+    pub swim_float_depth: f32,
+    /// How far below the surface the center of the player has to be to start swimming
+    pub swim_enter_depth: f32,
+    /// Stop swimming when the center of the player is less than this far below the surface
+    pub swim_exit_depth: f32,
+    /// Jumping out of the water is possible when less than this far below the surface
+    pub swim_surface_depth: f32,
+    pub swim_jump: f32,
+    pub swim_dash_velocity: f32,
+    pub swim_dash_cooldown: f32,
 }
 
 impl Default for PlayerControllerSettings {
@@ -101,8 +147,10 @@ impl Default for PlayerControllerSettings {
 
             air_jumps: 2,
             air_jump_forward_boost: 4.5,
+            infinite_air_jumps: false,
 
             dash_enabled: true,
+            infinite_dash: false,
             dash_velocity: 10.0,
             dash_height: 0.5,
 
@@ -117,6 +165,7 @@ impl Default for PlayerControllerSettings {
             slam_pause: 0.5,
             slam_velocity: 20.0,
             slam_jump_boost: 2.0,
+            slam_shockwave_radius: 4.0,
 
             wall_grab_enabled: true,
             wall_grab_min_normal: -0.1,
@@ -128,7 +177,37 @@ impl Default for PlayerControllerSettings {
             wall_jump_max_vertical: 5.0,
             wall_jump_add_horizontal: 5.0,
 
+            wall_run_enabled: true,
+            wall_run_min_speed: 5.0,
+            wall_run_time: 1.0,
+            wall_run_gravity_scale: 0.2,
+
+            grapple_enabled: true,
+            grapple_range: 16.0,
+            grapple_aim_cos: 0.75,
+            grapple_min_length: 3.0,
+            grapple_reel_speed: 2.0,
+
+            ledge_grab_enabled: true,
+            ledge_grab_reach: 0.3,
+            ledge_grab_height: 0.3,
+            ledge_grab_depth: 0.5,
+            ledge_climb_time: 0.35,
+            ledge_regrab_cooldown: 0.3,
+
             min_sword_bounce: 10.0,
+
+            swim_speed: 4.0,
+            swim_accel: 10.0,
+            swim_gravity_scale: 0.25,
+            swim_buoyancy: 12.0,
+            swim_float_depth: 0.6,
+            swim_enter_depth: 0.8,
+            swim_exit_depth: 0.3,
+            swim_surface_depth: 0.8,
+            swim_jump: 1.0,
+            swim_dash_velocity: 9.0,
+            swim_dash_cooldown: 0.6,
         }
     }
 }
@@ -136,11 +215,16 @@ impl Default for PlayerControllerSettings {
 #[derive(Component, Reflect, Default)]
 pub struct PlayerInput {
     pub movement: Vec2,
+    /// Movement direction relative to the camera, including pitch
+    pub swim: Vec3,
+    pub aim_origin: Vec3,
+    pub aim: Vec3,
     pub speed_modifier: f32,
     pub jump: ActionEvents,
     pub slide: ActionEvents,
     pub dash: ActionEvents,
     pub wall_grab: ActionEvents,
+    pub grapple: ActionEvents,
 }
 
 impl PlayerInput {
@@ -152,19 +236,24 @@ impl PlayerInput {
 
     fn gather(
         mut pi: Single<(&mut PlayerController, &mut PlayerInput, &PlayerState)>,
-        camera: Single<&PlayerCamera>,
+        camera: Single<(&PlayerCamera, &Transform)>,
         walk: Single<&ActionState, With<Action<Walk>>>,
         jump: Single<&ActionEvents, With<Action<Jump>>>,
         slide: Single<&ActionEvents, With<Action<Slide>>>,
         dash: Single<&ActionEvents, With<Action<Dash>>>,
         wall_grab: Single<&ActionEvents, With<Action<WallGrab>>>,
+        grapple: Single<&ActionEvents, With<Action<Grapple>>>,
         movement: Single<&Action<Move>>,
     ) {
         let dir = Vec3::new(movement.x, 0.0, -movement.y)
             .normalize_or_zero()
-            .rotate_y((camera.rotation + Angle::new(std::f32::consts::PI)).get());
+            .rotate_y((camera.0.rotation + Angle::new(std::f32::consts::PI)).get());
         pi.1.movement.x = dir.x;
         pi.1.movement.y = dir.z;
+        pi.1.swim =
+            (camera.1.rotation * Vec3::new(movement.x, 0.0, -movement.y)).normalize_or_zero();
+        pi.1.aim_origin = camera.1.translation;
+        pi.1.aim = camera.1.forward().as_vec3();
         pi.1.speed_modifier = movement.length();
         if pi.1.speed_modifier < 0.3 {
             pi.1.speed_modifier = 0.0;
@@ -183,6 +272,7 @@ impl PlayerInput {
         pi.1.slide = **slide;
         pi.1.dash = **dash;
         pi.1.wall_grab = **wall_grab;
+        pi.1.grapple = **grapple;
     }
 }
 
@@ -206,6 +296,9 @@ pub struct AirState {
     pub dashed: bool,
     pub jump_state: JumpState,
     pub coyote_countdown: f32,
+    pub ledge_cooldown: f32,
+    /// Normal of the last wall ran on, which can't be ran on again until landing
+    pub last_wall_run: Option<Dir3>,
 }
 
 impl AirState {
@@ -226,6 +319,56 @@ pub struct SlamState {
     timer: f32,
 }
 
+#[derive(Reflect, Clone)]
+pub struct WallRunState {
+    wall_normal: Dir3,
+    direction: Dir3,
+    speed: f32,
+    timer: f32,
+    prev_air_state: AirState,
+}
+
+impl WallRunState {
+    pub fn wall_normal(&self) -> Dir3 {
+        self.wall_normal
+    }
+}
+
+#[derive(Reflect, Clone)]
+pub struct LedgeHangState {
+    wall_normal: Dir3,
+    /// Where the player hangs from the ledge
+    hang: Vec3,
+    /// Where the player stands after climbing up
+    stand: Vec3,
+    climb_timer: Option<f32>,
+    prev_air_state: AirState,
+}
+
+impl LedgeHangState {
+    pub fn climbing(&self) -> bool {
+        self.climb_timer.is_some()
+    }
+}
+
+#[derive(Reflect, Clone)]
+pub struct SwingState {
+    anchor: Vec3,
+    length: f32,
+    prev_air_state: AirState,
+}
+
+impl SwingState {
+    pub fn anchor(&self) -> Vec3 {
+        self.anchor
+    }
+}
+
+#[derive(Reflect, Clone, Default)]
+pub struct SwimState {
+    dash_cooldown: f32,
+}
+
 #[derive(Reflect, Clone)]
 pub struct WallGrabState {
     wall_normal: Dir3,
@@ -248,6 +391,10 @@ pub enum PlayerState {
     Sliding(SlidingState),
     Slam(SlamState),
     WallGrab(WallGrabState),
+    WallRun(WallRunState),
+    LedgeHang(LedgeHangState),
+    Swing(SwingState),
+    Swim(SwimState),
 }
 
 impl Default for PlayerState {
@@ -262,7 +409,14 @@ impl PlayerState {
     }
 
     pub fn facing_locked(&self) -> bool {
-        matches!(self, Self::Sliding(_) | Self::Slam(_) | Self::WallGrab(_))
+        matches!(
+            self,
+            Self::Sliding(_)
+                | Self::Slam(_)
+                | Self::WallGrab(_)
+                | Self::WallRun(_)
+                | Self::LedgeHang(_)
+        )
     }
 }
 
@@ -285,12 +439,36 @@ pub struct PlayerController {
 impl PlayerController {
     fn step(
         query: Query<(MovementData, &mut PlayerState)>,
+        water: Query<&ColliderAabb, With<Water>>,
+        anchors: Query<(Entity, &GlobalTransform), With<GrappleAnchor>>,
         mut mas: MoveAndSlide,
         mut msg: MessageWriter<PlayerControllerMessage>,
         time: Res<Time>,
     ) {
         for (move_data, mut state) in query {
-            let mut mover = Mover::new(move_data, &mut mas, &mut msg, time.delta());
+            let water_surface = mas
+                .spatial_query
+                .shape_intersections(
+                    move_data.collider,
+                    move_data.transform.translation,
+                    Quat::default(),
+                    &SpatialQueryFilter::from_mask(GameLayer::Water),
+                )
+                .into_iter()
+                .filter_map(|entity| water.get(entity).ok())
+                .map(|aabb| aabb.max.y)
+                .reduce(f32::max);
+
+            let grapple_target = if move_data.settings.grapple_enabled
+                && move_data.input.grapple.contains(ActionEvents::START)
+            {
+                find_grapple_anchor(&move_data, &anchors, &mas.spatial_query)
+            } else {
+                None
+            };
+
+            let mut mover = Mover::new(move_data, &mut mas, &mut msg, time.delta(), water_surface);
+            mover.grapple_target = grapple_target;
 
             mover.step(&mut *state);
         }
@@ -345,6 +523,10 @@ struct Mover<'a, 'w1, 's1, 'w2, 's2, 'w3> {
     delta: Duration,
     dt: f32,
     filter: SpatialQueryFilter,
+    /// Height of the surface of the water the player is in
+    water_surface: Option<f32>,
+    /// Anchor the player started grappling this step
+    grapple_target: Option<Vec3>,
 }
 
 impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
@@ -353,6 +535,7 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
         mas: &'a mut MoveAndSlide<'w2, 's2>,
         msg: &'a mut MessageWriter<'w3, PlayerControllerMessage>,
         delta: Duration,
+        water_surface: Option<f32>,
     ) -> Self {
         Self {
             filter: SpatialQueryFilter::from_excluded_entities([data.entity])
@@ -362,6 +545,8 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
             msg,
             dt: delta.as_secs_f32(),
             delta,
+            water_surface,
+            grapple_target: None,
         }
     }
 
@@ -381,6 +566,7 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
 
         self.half_gravity(state);
 
+        self.update_water(state);
         self.update_state(state);
 
         self.snap_to_floor(state);
@@ -395,10 +581,41 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
             PlayerState::Sliding(_) => self.update_sliding(state),
             PlayerState::Slam(_) => self.update_slam(state),
             PlayerState::WallGrab(_) => self.update_wall_grab(state),
+            PlayerState::WallRun(_) => self.update_wall_run(state),
+            PlayerState::LedgeHang(_) => self.update_ledge_hang(state),
+            PlayerState::Swing(_) => self.update_swing(state),
+            PlayerState::Swim(_) => self.update_swim(state),
+        }
+    }
+
+    fn water_depth(&self) -> Option<f32> {
+        self.water_surface
+            .map(|surface| surface - self.transform.translation.y)
+    }
+
+    fn update_water(&mut self, state: &mut PlayerState) {
+        let swimming = matches!(state, PlayerState::Swim(_));
+
+        match self.water_depth() {
+            Some(depth) if !swimming && depth >= self.settings.swim_enter_depth => {
+                self.velocity.y /= 2.0;
+                *state = PlayerState::Swim(default());
+            }
+            Some(depth) if swimming && depth < self.settings.swim_exit_depth => {
+                *state = PlayerState::Air(default());
+            }
+            None if swimming => {
+                *state = PlayerState::Air(default());
+            }
+            _ => {}
         }
     }
 
     fn update_grounded(&mut self, state: &mut PlayerState) {
+        if self.try_grapple(state) {
+            return;
+        }
+
         let PlayerState::Grounded(gstate) = state else {
             return;
         };
@@ -452,10 +669,16 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
                 direction: Vec2::new(dir.y, dir.x),
                 timer: 0.0,
             });
+
+            self.msg.write(PlayerControllerMessage::Slide);
         }
     }
 
     fn update_air(&mut self, state: &mut PlayerState) {
+        if self.try_grapple(state) {
+            return;
+        }
+
         let PlayerState::Air(astate) = state else {
             return;
         };
@@ -463,6 +686,7 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
         self.air_friction();
 
         astate.coyote_countdown = (astate.coyote_countdown - self.dt).max(0.0);
+        astate.ledge_cooldown = (astate.ledge_cooldown - self.dt).max(0.0);
 
         if astate.jump_state == JumpState::Normal
             && self.data.velocity.y > 0.0
@@ -483,10 +707,29 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
             if let Some(wall_normal) = self.try_wall_grab() {
                 let prev_state = astate.clone();
                 *state = PlayerState::WallGrab(WallGrabState::new(wall_normal, prev_state));
+                self.msg.write(PlayerControllerMessage::WallGrab);
                 return;
             }
         }
 
+        // Wall Run
+        if self.data.settings.wall_run_enabled
+            && let Some(wstate) = self.try_wall_run(astate)
+        {
+            *state = PlayerState::WallRun(wstate);
+            return;
+        }
+
+        // Ledge Grab
+        if self.data.settings.ledge_grab_enabled
+            && astate.ledge_cooldown <= 0.0
+            && self.data.velocity.y <= 0.0
+            && let Some(lstate) = self.try_ledge_grab(astate.clone())
+        {
+            *state = PlayerState::LedgeHang(lstate);
+            return;
+        }
+
         // Slam
         if self.data.settings.slam_enabled && self.data.input.slide.contains(ActionEvents::START) {
             *state = PlayerState::Slam(default());
@@ -495,7 +738,7 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
 
         // Dash
         if self.data.settings.dash_enabled
-            && !astate.dashed
+            && (!astate.dashed || self.data.settings.infinite_dash)
             && self.data.input.dash.contains(ActionEvents::START)
         {
             astate.dashed = true;
@@ -510,12 +753,17 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
             self.data.velocity.0 += dir * boost;
             self.data.velocity.y =
                 (2.0 * self.data.settings.gravity * self.data.settings.dash_height).sqrt();
+
+            self.msg.write(PlayerControllerMessage::Dash);
         }
 
         // Jumping
         if self.data.input.jump.contains(ActionEvents::START) {
-            if astate.air_jumps < self.data.settings.air_jumps && astate.coyote_countdown <= 0.0 {
-                astate.air_jumps += 1;
+            if (astate.air_jumps < self.data.settings.air_jumps
+                || self.data.settings.infinite_air_jumps)
+                && astate.coyote_countdown <= 0.0
+            {
+                astate.air_jumps = astate.air_jumps.saturating_add(1);
 
                 // Air Jump
                 self.data.velocity.y =
@@ -628,6 +876,8 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
                 dashed: air_state.dashed,
                 jump_state: JumpState::None,
                 coyote_countdown: 0.0,
+                ledge_cooldown: 0.0,
+                last_wall_run: None,
             });
 
             self.apply_velocity(false, |_| {});
@@ -656,6 +906,8 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
                 dashed: air_state.dashed,
                 jump_state: JumpState::Normal,
                 coyote_countdown: 0.0,
+                ledge_cooldown: 0.0,
+                last_wall_run: None,
             });
 
             self.apply_velocity(false, |_| {});
@@ -689,12 +941,290 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
                 dashed: air_state.dashed,
                 jump_state: JumpState::None,
                 coyote_countdown: 0.0,
+                ledge_cooldown: 0.0,
+                last_wall_run: None,
+            });
+
+            self.apply_velocity(false, |_| {});
+        }
+    }
+
+    fn update_wall_run(&mut self, state: &mut PlayerState) {
+        let PlayerState::WallRun(wstate) = state else {
+            return;
+        };
+
+        wstate.timer += self.dt;
+
+        // Update facing
+        self.pc.facing = Angle::new(vec2(wstate.direction.z, wstate.direction.x).to_angle());
+
+        // Switch to wall grab
+        if self.settings.wall_grab_enabled && self.input.wall_grab.contains(ActionEvents::FIRE) {
+            let prev_state = wstate.prev_air_state.clone();
+            *state = PlayerState::WallGrab(WallGrabState::new(wstate.wall_normal, prev_state));
+            self.msg.write(PlayerControllerMessage::WallGrab);
+            return;
+        }
+
+        // Wall Jump
+        if self.input.jump.contains(ActionEvents::START) {
+            let air_state = wstate.prev_air_state.clone();
+
+            self.velocity.y = self.velocity.y.max(0.0)
+                + (self.settings.gravity * 2.0 * self.settings.wall_jump_add_vertical).sqrt();
+            let hadd = wstate.wall_normal * self.settings.wall_jump_add_horizontal;
+            self.velocity.0 += hadd;
+
+            *state = PlayerState::Air(AirState {
+                jump_state: JumpState::Normal,
+                ..air_state
+            });
+
+            self.apply_velocity(false, |_| {});
+
+            return;
+        }
+
+        // Keep horizontal speed along the wall
+        let hvel = wstate.direction * wstate.speed;
+        self.velocity.x = hvel.x;
+        self.velocity.z = hvel.z;
+
+        self.apply_velocity(false, |_| {});
+
+        // Stop when out of time, blocked, or the wall ends
+        let wall = self.mas.spatial_query.cast_shape(
+            self.collider,
+            self.transform.translation,
+            Quat::default(),
+            -wstate.wall_normal,
+            &ShapeCastConfig {
+                max_distance: self.settings.wall_grab_max_wall_distance,
+                ..default()
+            },
+            &self.filter,
+        );
+
+        if wstate.timer >= self.settings.wall_run_time
+            || self.velocity.xz().length() < self.settings.wall_run_min_speed
+            || wall.is_none()
+        {
+            let air_state = wstate.prev_air_state.clone();
+
+            *state = PlayerState::Air(AirState {
+                jump_state: JumpState::None,
+                ..air_state
+            });
+        }
+    }
+
+    fn update_ledge_hang(&mut self, state: &mut PlayerState) {
+        let PlayerState::LedgeHang(lstate) = state else {
+            return;
+        };
+
+        self.velocity.0 = Vec3::ZERO;
+
+        // Update facing
+        self.pc.facing = Angle::new(vec2(-lstate.wall_normal.z, -lstate.wall_normal.x).to_angle());
+
+        // Climb up, then forward
+        if let Some(timer) = &mut lstate.climb_timer {
+            *timer += self.dt;
+            let t = (*timer / self.settings.ledge_climb_time).min(1.0);
+
+            let above = vec3(lstate.hang.x, lstate.stand.y, lstate.hang.z);
+            self.transform.translation = if t < 0.5 {
+                lstate.hang.lerp(above, t * 2.0)
+            } else {
+                above.lerp(lstate.stand, (t - 0.5) * 2.0)
+            };
+
+            if t >= 1.0 {
+                *state = PlayerState::Grounded(default());
+            }
+
+            return;
+        }
+
+        let away = Dir3::new(vec3(lstate.wall_normal.x, 0.0, lstate.wall_normal.z))
+            .unwrap_or(lstate.wall_normal);
+        let pulling_away = speed_towards_dir(
+            vec3(self.input.movement.x, 0.0, self.input.movement.y),
+            away,
+        ) > 0.5;
+
+        // Jump off
+        if self.input.jump.contains(ActionEvents::START) && pulling_away {
+            let air_state = lstate.prev_air_state.clone();
+
+            self.velocity.y =
+                (self.settings.gravity * 2.0 * self.settings.wall_jump_add_vertical).sqrt();
+            let hadd = away * self.settings.wall_jump_add_horizontal;
+            self.velocity.0 += hadd;
+
+            *state = PlayerState::Air(AirState {
+                jump_state: JumpState::Normal,
+                ..air_state
             });
 
             self.apply_velocity(false, |_| {});
 
             return;
         }
+
+        // Climb up
+        if self.input.jump.contains(ActionEvents::START) {
+            lstate.climb_timer = Some(0.0);
+            return;
+        }
+
+        // Drop down, or fall if the wall is gone
+        let wall = self.mas.spatial_query.cast_shape(
+            self.collider,
+            self.transform.translation,
+            Quat::default(),
+            -away,
+            &ShapeCastConfig {
+                max_distance: self.settings.wall_grab_max_wall_distance,
+                ..default()
+            },
+            &self.filter,
+        );
+
+        if self.input.slide.contains(ActionEvents::START) || wall.is_none() {
+            let air_state = lstate.prev_air_state.clone();
+
+            *state = PlayerState::Air(AirState {
+                jump_state: JumpState::None,
+                ledge_cooldown: self.settings.ledge_regrab_cooldown,
+                ..air_state
+            });
+        }
+    }
+
+    fn update_swing(&mut self, state: &mut PlayerState) {
+        let PlayerState::Swing(sstate) = state else {
+            return;
+        };
+
+        // Let go, keeping momentum
+        if !self.input.grapple.contains(ActionEvents::FIRE) {
+            let air_state = sstate.prev_air_state.clone();
+
+            *state = PlayerState::Air(AirState {
+                jump_state: JumpState::None,
+                ..air_state
+            });
+
+            self.apply_velocity(false, |_| {});
+
+            return;
+        }
+
+        sstate.length = (sstate.length - self.settings.grapple_reel_speed * self.dt)
+            .max(self.settings.grapple_min_length);
+
+        self.air_move();
+
+        // Remove velocity away from the anchor while the rope is taut
+        let offset = self.transform.translation - sstate.anchor;
+        if offset.length() >= sstate.length
+            && let Ok(out) = Dir3::new(offset)
+        {
+            let away = self.velocity.dot(*out);
+            if away > 0.0 {
+                self.velocity.0 -= out * away;
+            }
+        }
+
+        self.apply_velocity(false, |_| {});
+
+        // Pull the player back to the end of the rope
+        let offset = self.transform.translation - sstate.anchor;
+        if offset.length() > sstate.length {
+            let correction =
+                sstate.anchor + offset.normalize() * sstate.length - self.transform.translation;
+
+            let distance = match self.mas.cast_move(
+                self.collider,
+                self.transform.translation,
+                Quat::default(),
+                correction,
+                0.01,
+                &self.filter,
+            ) {
+                Some(hit) => hit.distance,
+                None => correction.length(),
+            };
+
+            self.transform.translation += correction.normalize_or_zero() * distance;
+        }
+    }
+
+    fn update_swim(&mut self, state: &mut PlayerState) {
+        let PlayerState::Swim(sstate) = state else {
+            return;
+        };
+
+        let depth = self.water_depth().unwrap_or_default();
+
+        sstate.dash_cooldown = (sstate.dash_cooldown - self.dt).max(0.0);
+
+        // Jump out of the water
+        if self.input.jump.contains(ActionEvents::START)
+            && depth <= self.settings.swim_surface_depth
+        {
+            self.velocity.y = (2.0 * self.settings.gravity * self.settings.swim_jump).sqrt();
+            *state = PlayerState::Air(AirState {
+                jump_state: JumpState::Normal,
+                ..default()
+            });
+
+            self.apply_velocity(false, |_| {});
+
+            return;
+        }
+
+        let mut dir = self.input.swim * self.input.speed_modifier;
+        if self.input.jump.contains(ActionEvents::FIRE) {
+            dir.y += 1.0;
+        }
+        if self.input.slide.contains(ActionEvents::FIRE) {
+            dir.y -= 1.0;
+        }
+        let target = dir.clamp_length_max(1.0) * self.settings.swim_speed;
+
+        self.velocity.0 = self
+            .velocity
+            .move_towards(target, self.settings.swim_accel * self.dt);
+
+        // Buoyancy, cancels out gravity at the floating depth
+        if dir.y == 0.0 && depth > 0.0 {
+            let offset = (depth - self.settings.swim_float_depth).clamp(-1.0, 1.0);
+            let lift = self.settings.gravity * self.settings.swim_gravity_scale
+                + offset * self.settings.swim_buoyancy;
+            self.velocity.y += lift * self.dt;
+        }
+
+        // Underwater dash
+        if self.settings.dash_enabled
+            && sstate.dash_cooldown <= 0.0
+            && self.input.dash.contains(ActionEvents::START)
+        {
+            sstate.dash_cooldown = self.settings.swim_dash_cooldown;
+
+            let facing = Vec2::from_angle(self.pc.facing.get());
+            let dir = Dir3::new(self.input.swim)
+                .unwrap_or(Dir3::new(vec3(facing.y, 0.0, facing.x)).unwrap_or(Dir3::Z));
+
+            self.velocity.0 = dir * self.settings.swim_dash_velocity;
+
+            self.msg.write(PlayerControllerMessage::Dash);
+        }
+
+        self.apply_velocity(false, |_| {});
     }
 
     fn air_friction(&mut self) {
@@ -744,7 +1274,77 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
         });
     }
 
+    /// Starts swinging if an anchor was targeted this step
+    fn try_grapple(&mut self, state: &mut PlayerState) -> bool {
+        let Some(anchor) = self.grapple_target.take() else {
+            return false;
+        };
+
+        let prev_air_state = match state {
+            PlayerState::Air(astate) => astate.clone(),
+            _ => AirState::default(),
+        };
+
+        *state = PlayerState::Swing(SwingState {
+            anchor,
+            length: self.transform.translation.distance(anchor),
+            prev_air_state,
+        });
+
+        true
+    }
+
     fn try_wall_grab(&mut self) -> Option<Dir3> {
+        let (dir, normal) = self.find_wall()?;
+
+        let cur_vel = self.velocity.0;
+
+        self.velocity.0 = dir * self.settings.wall_grab_max_wall_distance;
+        self.apply_velocity(false, |_| {});
+        self.velocity.0 = cur_vel;
+
+        Some(normal)
+    }
+
+    fn try_wall_run(&mut self, astate: &AirState) -> Option<WallRunState> {
+        let (_, normal) = self.find_wall()?;
+
+        if astate
+            .last_wall_run
+            .is_some_and(|last| last.dot(*normal) > 0.99)
+        {
+            return None;
+        }
+
+        // Only run along the wall, not into it
+        let hvel = vec3(self.velocity.x, 0.0, self.velocity.z);
+        let along = hvel.reject_from(normal.as_vec3());
+        let along = vec3(along.x, 0.0, along.z);
+
+        let speed = along.length();
+        if speed < self.settings.wall_run_min_speed {
+            return None;
+        }
+
+        let direction = Dir3::new(along).ok()?;
+
+        self.velocity.y = self.velocity.y.max(0.0);
+
+        Some(WallRunState {
+            wall_normal: normal,
+            direction,
+            speed,
+            timer: 0.0,
+            prev_air_state: AirState {
+                last_wall_run: Some(normal),
+                ..astate.clone()
+            },
+        })
+    }
+
+    /// Finds the wall the player is facing the most.
+    /// Returns the direction of the wall and its normal
+    fn find_wall(&self) -> Option<(Dir3, Dir3)> {
         let facing = Vec2::from_angle(self.pc.facing.get());
         let facing = vec3(facing.y, 0.0, facing.x);
 
@@ -792,22 +1392,105 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
             }
         }
 
-        let Some((_, dir, normal)) = direction else {
+        direction.map(|(_, dir, normal)| (dir, normal))
+    }
+
+    /// Looks for a ledge in front of the player, and moves the player to hang from it
+    fn try_ledge_grab(&mut self, prev_air_state: AirState) -> Option<LedgeHangState> {
+        const PROBE_RADIUS: f32 = 0.05;
+
+        let facing = Vec2::from_angle(self.pc.facing.get());
+        let facing = Dir3::new(vec3(facing.y, 0.0, facing.x)).ok()?;
+
+        // Find the wall in front of the player
+        let wall = self.mas.spatial_query.cast_shape(
+            self.collider,
+            self.transform.translation,
+            Quat::default(),
+            facing,
+            &ShapeCastConfig {
+                max_distance: self.settings.ledge_grab_reach,
+                ..default()
+            },
+            &self.filter,
+        )?;
+
+        let normal = Dir3::new(wall.normal1).ok()?;
+
+        if normal.y < self.settings.wall_grab_min_normal
+            || normal.y > self.settings.wall_grab_max_normal
+        {
             return None;
-        };
+        }
 
-        let cur_vel = self.velocity.0;
+        let into_wall = Dir3::new(vec3(-normal.x, 0.0, -normal.z)).ok()?;
 
-        self.velocity.0 = dir * self.settings.wall_grab_max_wall_distance;
-        self.apply_velocity(false, |_| {});
-        self.velocity.0 = cur_vel;
+        if speed_towards_dir(facing.as_vec3(), into_wall) < 0.5 {
+            return None;
+        }
 
-        Some(normal)
+        // Find the top of the wall, starting above the player's head
+        let head = self.transform.translation.y + PLAYER_HEIGHT / 2.0;
+
+        let mut origin = self.transform.translation
+            + facing * wall.distance
+            + into_wall * (PLAYER_WIDTH / 2.0 + PROBE_RADIUS * 2.0);
+        origin.y = head + self.settings.ledge_grab_height;
+
+        let top = self.mas.spatial_query.cast_shape(
+            &Collider::sphere(PROBE_RADIUS),
+            origin,
+            Quat::default(),
+            Dir3::NEG_Y,
+            &ShapeCastConfig {
+                max_distance: self.settings.ledge_grab_height + self.settings.ledge_grab_depth,
+                ..default()
+            },
+            &self.filter,
+        )?;
+
+        if top.distance <= 0.0 || top.normal1.y < self.settings.min_floor_angle {
+            return None;
+        }
+
+        let ledge = origin.y - top.distance - PROBE_RADIUS;
+
+        // Make sure there is room to stand on the ledge
+        let mut stand = origin + into_wall * (PLAYER_WIDTH / 2.0);
+        stand.y = ledge + PLAYER_HEIGHT / 2.0 + 0.01;
+
+        if !self
+            .mas
+            .spatial_query
+            .shape_intersections(self.collider, stand, Quat::default(), &self.filter)
+            .is_empty()
+        {
+            return None;
+        }
+
+        let mut hang = self.transform.translation + facing * wall.distance;
+        hang.y = ledge - PLAYER_HEIGHT / 2.0;
+
+        self.transform.translation = hang;
+
+        Some(LedgeHangState {
+            wall_normal: normal,
+            hang,
+            stand,
+            climb_timer: None,
+            prev_air_state,
+        })
     }
 
     fn half_gravity(&mut self, state: &mut PlayerState) {
+        let gravity = match state {
+            PlayerState::Swim(_) => self.settings.gravity * self.settings.swim_gravity_scale,
+            PlayerState::WallRun(_) => self.settings.gravity * self.settings.wall_run_gravity_scale,
+            _ => self.settings.gravity,
+        };
+
         if !state.grounded() {
-            self.velocity.y -= self.settings.gravity * 0.5 * self.dt;
+            self.velocity.y -= gravity * 0.5 * self.dt;
         }
 
         if self.velocity.y < -self.settings.terminal_velocity {
@@ -850,6 +1533,13 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
     }
 
     fn check_grounded(&mut self, state: &mut PlayerState) {
+        if matches!(
+            state,
+            PlayerState::Swim(_) | PlayerState::LedgeHang(_) | PlayerState::Swing(_)
+        ) {
+            return;
+        }
+
         let mut grounded = false;
 
         if self.velocity.y <= self.settings.maximum_grounded_up_velocity {
@@ -874,6 +1564,9 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
         }
 
         if grounded && !state.grounded() {
+            if !matches!(state, PlayerState::Slam(_)) {
+                self.msg.write(PlayerControllerMessage::Land);
+            }
             *state = PlayerState::Grounded(default());
         } else if !grounded && state.grounded() {
             *state = PlayerState::Air(AirState::default().with_coyote(&self.settings));
@@ -993,6 +1686,52 @@ impl<'a, 'w, 's, 'w2, 's2, 'w3> Mover<'a, 'w, 's, 'w2, 's2, 'w3> {
     }
 }
 
+/// Finds the anchor closest to the center of the camera that the player can see
+fn find_grapple_anchor(
+    data: &MovementDataItem,
+    anchors: &Query<(Entity, &GlobalTransform), With<GrappleAnchor>>,
+    spatial_query: &SpatialQuery,
+) -> Option<Vec3> {
+    const ANCHOR_CLEARANCE: f32 = 0.5;
+
+    let origin = data.transform.translation;
+
+    anchors
+        .iter()
+        .filter_map(|(entity, transform)| {
+            let anchor = transform.translation();
+
+            let offset = anchor - origin;
+            let distance = offset.length();
+            if distance > data.settings.grapple_range {
+                return None;
+            }
+
+            let alignment = (anchor - data.input.aim_origin)
+                .normalize_or_zero()
+                .dot(data.input.aim);
+            if alignment < data.settings.grapple_aim_cos {
+                return None;
+            }
+
+            let blocked = spatial_query.cast_ray(
+                origin,
+                Dir3::new(offset).ok()?,
+                (distance - ANCHOR_CLEARANCE).max(0.0),
+                true,
+                &SpatialQueryFilter::from_excluded_entities([data.entity, entity])
+                    .with_mask(GameLayer::Level),
+            );
+            if blocked.is_some() {
+                return None;
+            }
+
+            Some((alignment, anchor))
+        })
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, anchor)| anchor)
+}
+
 fn speed_towards_dir(speed: Vec3, dir: Dir3) -> f32 {
     let angle = speed.angle_between(dir.as_vec3());
 
@@ -1004,11 +1743,17 @@ fn speed_towards_dir(speed: Vec3, dir: Dir3) -> f32 {
     }
 }
 
-#[derive(Message, Clone, PartialEq)]
+#[derive(Message, Deserialize, Clone, PartialEq, Debug)]
 pub enum PlayerControllerMessage {
     GroundJump,
     CoyoteTimeJump,
     CoyoteFrictionJump,
     AirJump,
+    Land,
+    Dash,
+    Slide,
     Slam(Vec3),
+    WallGrab,
+    /// Bounced off of something with the sword
+    Pogo,
 }
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use dreamseeker_util::add_asset::{AddMaterial, AddMesh};
+
+use super::controller::PlayerState;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, Rope::update);
+}
+
+/// A point the grappling hook can attach to
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+#[require(Transform)]
+pub struct GrappleAnchor;
+
+#[derive(Component, Reflect, Default)]
+#[require(Name::new("Rope"), Visibility::Hidden)]
+pub struct Rope;
+
+impl Rope {
+    pub fn bundle() -> impl Bundle {
+        (
+            Self,
+            AddMesh(Cylinder::new(0.03, 1.0)),
+            AddMaterial(StandardMaterial {
+                base_color: Color::linear_rgb(0.4, 0.25, 0.1),
+                perceptual_roughness: 1.0,
+                ..default()
+            }),
+        )
+    }
+
+    fn update(
+        player: Single<(&Transform, &PlayerState), Without<Rope>>,
+        mut rope: Single<(&mut Transform, &mut Visibility), With<Rope>>,
+    ) {
+        let PlayerState::Swing(sstate) = player.1 else {
+            *rope.1 = Visibility::Hidden;
+            return;
+        };
+
+        // The rope is a child of the player, so it is positioned relative to it
+        let offset = sstate.anchor() - player.0.translation;
+
+        *rope.1 = Visibility::Inherited;
+        rope.0.translation = offset / 2.0;
+        rope.0.rotation = Quat::from_rotation_arc(Vec3::Y, offset.normalize_or(Vec3::Y));
+        rope.0.scale = vec3(1.0, offset.length(), 1.0);
+    }
+}
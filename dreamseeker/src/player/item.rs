@@ -13,16 +13,27 @@ use bevy_flurx::{
     task::ReactorTask,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
     GameState, Sounds,
+    assist::Assists,
     collision::GameLayer,
+    progress::CollectibleId,
+    save::SaveData,
+    telemetry::Telemetry,
     ui::screen::{
         ScreenCommandsExt,
         item::{ItemDescriptionScreen, item_description},
     },
 };
 
-use super::{Player, controller::PlayerControllerSettings};
+use super::{
+    Player,
+    cinematic::{CameraPath, Cinematic},
+    controller::PlayerControllerSettings,
+    shockwave::{SlamHit, SlamTarget},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_observer(Chest::on_hit).add_systems(
@@ -63,6 +74,8 @@ impl Token {
         event: On<CollisionStart>,
         mut player: Query<&mut Player>,
         mut transform: Query<&mut Position>,
+        ids: Query<&CollectibleId>,
+        mut save: ResMut<SaveData>,
         sounds: Res<Sounds>,
         mut cmd: Commands,
     ) {
@@ -71,6 +84,9 @@ impl Token {
         };
 
         player.dream_tokens += 1;
+        if let Ok(id) = ids.get(event.collider1) {
+            save.found.insert(id.0.clone());
+        }
 
         cmd.spawn((AudioPlayer(sounds.token.clone()), PlaybackSettings::DESPAWN));
 
@@ -105,8 +121,13 @@ pub struct PlayerItems(pub HashSet<Item>);
 
 impl PlayerItems {
     fn on_update(
-        mut player: Single<(&mut PlayerControllerSettings, &PlayerItems), Changed<PlayerItems>>,
+        mut player: Single<(&mut PlayerControllerSettings, Ref<PlayerItems>)>,
+        assists: Res<Assists>,
     ) {
+        if !player.1.is_changed() && !assists.is_changed() {
+            return;
+        }
+
         player.0.air_jumps = 0;
         for cloud in [Item::Cloud1, Item::Cloud2, Item::Cloud3] {
             if player.1.contains(&cloud) {
@@ -117,6 +138,10 @@ impl PlayerItems {
         player.0.slide_enabled = player.1.contains(&Item::Ice);
         player.0.slam_enabled = player.1.contains(&Item::Anvil);
         player.0.wall_grab_enabled = player.1.contains(&Item::Scroll);
+        player.0.wall_run_enabled = player.1.contains(&Item::Shoes);
+        player.0.grapple_enabled = player.1.contains(&Item::Hook);
+
+        assists.apply(&mut player.0);
     }
 
     fn give_all(mut player: Single<&mut PlayerItems>, keys: Res<ButtonInput<KeyCode>>) {
@@ -128,7 +153,21 @@ impl PlayerItems {
     }
 }
 
-#[derive(Component, Reflect, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Component,
+    Reflect,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
 #[reflect(Component, Default)]
 pub enum Item {
     #[default]
@@ -140,10 +179,12 @@ pub enum Item {
     Anvil,
     Scroll,
     Sword,
+    Shoes,
+    Hook,
 }
 
 impl Item {
-    pub const ALL: [Self; 8] = [
+    pub const ALL: [Self; 10] = [
         Self::Cloud1,
         Self::Cloud2,
         Self::Cloud3,
@@ -152,44 +193,42 @@ impl Item {
         Self::Anvil,
         Self::Scroll,
         Self::Sword,
+        Self::Shoes,
+        Self::Hook,
     ];
 
-    pub fn name(&self) -> &'static str {
+    /// Key of the name in the string tables
+    pub fn name_key(&self) -> &'static str {
         match self {
-            Self::Cloud1 | Self::Cloud2 | Self::Cloud3 => "Cloud",
-            Self::Rocket => "Rocket",
-            Self::Ice => "Slime",
-            Self::Anvil => "Anvil",
-            Self::Scroll => "Ninja Scroll",
-            Self::Sword => "Sword",
+            Self::Cloud1 | Self::Cloud2 | Self::Cloud3 => "item.cloud.name",
+            Self::Rocket => "item.rocket.name",
+            Self::Ice => "item.ice.name",
+            Self::Anvil => "item.anvil.name",
+            Self::Scroll => "item.scroll.name",
+            Self::Sword => "item.sword.name",
+            Self::Shoes => "item.shoes.name",
+            Self::Hook => "item.hook.name",
         }
     }
 
-    pub fn description(&self) -> &'static str {
+    /// Key of the description in the string tables
+    pub fn description_key(&self) -> &'static str {
         match self {
-            Self::Cloud1 | Self::Cloud2 | Self::Cloud3 => {
-                "You gained an additional jump!\nYou can jump again while you are in the air"
-            }
-            Self::Rocket => "You can air dash!\nPress Right-Click / Y in the air to dash forward",
-            Self::Ice => {
-                "You can slide!\nPress Shift / A to slide along the ground. Jumping out of a slide gives you extra momentum"
-            }
-            Self::Anvil => {
-                "You can slam!\nPress Shift / A in the air to slam into the ground. Jumping after a slam gives you extra height"
-            }
-            Self::Scroll => {
-                "You can grab on to walls!\nHold Control / Right Trigger to grab a wall\nHolding a wall refreshes your air jumps"
-            }
-            Self::Sword => {
-                "You can pogo off of those RED spheres!\nPress Left Click / X in the air to use your sword\nPogoing refreshes all your abilities"
-            }
+            Self::Cloud1 | Self::Cloud2 | Self::Cloud3 => "item.cloud.description",
+            Self::Rocket => "item.rocket.description",
+            Self::Ice => "item.ice.description",
+            Self::Anvil => "item.anvil.description",
+            Self::Scroll => "item.scroll.description",
+            Self::Sword => "item.sword.description",
+            Self::Shoes => "item.shoes.description",
+            Self::Hook => "item.hook.description",
         }
     }
 }
 
 #[derive(Component, Reflect, Default)]
 #[reflect(Component, Default)]
-#[require(Name::new("Chest"), Item, ChestData)]
+#[require(Name::new("Chest"), Item, ChestData, SlamTarget)]
 #[component(on_add)]
 pub struct Chest;
 
@@ -197,6 +236,15 @@ pub struct Chest;
 struct ChestData {
     open: AnimationNodeIndex,
     aplayer: Option<Entity>,
+    /// Set when the item get cutscene starts, so hitting and slamming can't both open it
+    opened: bool,
+}
+
+impl ChestData {
+    /// Marks the chest as opened, returning false if it already was
+    fn open(&mut self) -> bool {
+        !std::mem::replace(&mut self.opened, true)
+    }
 }
 
 impl Chest {
@@ -206,6 +254,7 @@ impl Chest {
             .commands()
             .entity(ctx.entity)
             .observe(Self::on_load)
+            .observe(Self::on_slam)
             .insert(scene);
     }
 
@@ -237,14 +286,17 @@ impl Chest {
             }
         }
 
-        cmd.entity(event.entity)
-            .insert(ChestData { open, aplayer: ap });
+        cmd.entity(event.entity).insert(ChestData {
+            open,
+            aplayer: ap,
+            ..default()
+        });
     }
 
     fn on_hit(
         event: On<CollisionStart>,
         parent: Query<&ChildOf>,
-        q_chest: Query<(&Chest, &Item)>,
+        mut q_chest: Query<(&Item, &mut ChestData), With<Chest>>,
         player: Query<&Player>,
         mut cmd: Commands,
     ) {
@@ -253,16 +305,36 @@ impl Chest {
                 continue;
             }
 
-            let Ok((_, item)) = q_chest.get(entity) else {
+            let Ok((&item, mut chest)) = q_chest.get_mut(entity) else {
                 continue;
             };
-            let item = item.clone();
+            if !chest.open() {
+                continue;
+            }
 
             cmd.spawn(Reactor::schedule(move |task| {
                 item_get_cutscene(task, entity, item)
             }));
         }
     }
+
+    fn on_slam(
+        event: On<SlamHit>,
+        mut q_chest: Query<(&Item, &mut ChestData), With<Chest>>,
+        mut cmd: Commands,
+    ) {
+        let entity = event.entity;
+        let Ok((&item, mut chest)) = q_chest.get_mut(entity) else {
+            return;
+        };
+        if !chest.open() {
+            return;
+        }
+
+        cmd.spawn(Reactor::schedule(move |task| {
+            item_get_cutscene(task, entity, item)
+        }));
+    }
 }
 
 async fn item_get_cutscene(task: ReactorTask, chest: Entity, item: Item) {
@@ -272,10 +344,22 @@ async fn item_get_cutscene(task: ReactorTask, chest: Entity, item: Item) {
             move |mut state: ResMut<NextState<GameState>>,
                   q_chest: Query<&ChestData>,
                   mut aplayer: Query<&mut AnimationPlayer>,
+                  transforms: Query<&GlobalTransform>,
+                  player: Single<&Transform, With<Player>>,
+                  mut cinematic: ResMut<Cinematic>,
                   mut cmd: Commands,
                   sounds: Res<Sounds>|
                   -> Option<()> {
                 state.set(GameState::Cutscene);
+
+                if let Ok(transform) = transforms.get(chest) {
+                    let center = transform.translation() + Vec3::Y * 0.5;
+                    cinematic.start(CameraPath {
+                        hold: true,
+                        ..CameraPath::orbit(center, player.translation, 3.5, 1.5, 3.0)
+                    });
+                }
+
                 let chest = q_chest.get(chest).ok()?;
 
                 let mut aplayer = chest.aplayer.and_then(|ap| aplayer.get_mut(ap).ok())?;
@@ -337,11 +421,23 @@ async fn item_get_cutscene(task: ReactorTask, chest: Entity, item: Item) {
         PreUpdate,
         once::run(
             move |mut state: ResMut<NextState<GameState>>,
-                  mut player: Single<&mut PlayerItems>,
+                  mut player: Single<(&mut PlayerItems, &Transform)>,
+                  mut cinematic: ResMut<Cinematic>,
+                  ids: Query<&CollectibleId>,
+                  mut save: ResMut<SaveData>,
+                  mut telemetry: MessageWriter<Telemetry>,
                   mut cmd: Commands| {
+                if let Ok(id) = ids.get(chest) {
+                    save.found.insert(id.0.clone());
+                }
                 cmd.entity(chest).despawn();
+                cinematic.stop();
                 state.set(GameState::InGame);
-                player.insert(item);
+                player.0.insert(item);
+                telemetry.write(Telemetry::Item {
+                    item,
+                    position: player.1.translation.to_array(),
+                });
             },
         ),
     )
@@ -1,8 +1,11 @@
 use std::{f32::consts::PI, time::Duration};
 
-use avian3d::prelude::{Collider, CollisionStart, LinearVelocity, Position};
+use avian3d::prelude::{
+    Collider, CollisionStart, LinearVelocity, Position, SpatialQuery, SpatialQueryFilter,
+};
 use bevy::{
     audio::{PlaybackMode, Volume},
+    pbr::decal::{ForwardDecal, ForwardDecalMaterial, ForwardDecalMaterialExt},
     prelude::*,
     scene::SceneInstanceReady,
 };
@@ -12,6 +15,7 @@ use dreamseeker_util::{construct::Make, observers};
 
 use crate::{
     GameState, Sounds,
+    collision::GameLayer,
     input::player::Attack,
     trigger::{Checkpoint, InitialSpawn},
     ui::trans::{EndTransition, Transition},
@@ -22,23 +26,36 @@ use self::{
     controller::{
         JumpState, PlayerController, PlayerControllerMessage, PlayerControllerSettings, PlayerState,
     },
+    grapple::Rope,
     item::{Item, PlayerItems},
     sword::Sword,
 };
 
 pub mod camera;
-mod controller;
+pub mod cinematic;
+pub mod controller;
+mod grapple;
 pub mod item;
+mod particles;
+pub mod shockwave;
 mod sword;
 
 const PLAYER_HEIGHT: f32 = 1.7;
 const PLAYER_WIDTH: f32 = 0.35;
 
+const SHADOW_SIZE: f32 = 0.8;
+const SHADOW_MAX_DISTANCE: f32 = 15.0;
+const SHADOW_FADE_DISTANCE: f32 = 5.0;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         self::camera::plugin,
+        self::cinematic::plugin,
         self::controller::plugin,
+        self::grapple::plugin,
         self::item::plugin,
+        self::particles::plugin,
+        self::shockwave::plugin,
         self::sword::plugin,
     ))
     .add_message::<Respawn>()
@@ -47,7 +64,7 @@ pub(super) fn plugin(app: &mut App) {
         (
             Player::rotate_model,
             Player::animate,
-            // Player::update_shadow,
+            Player::update_shadow,
             Player::play_sounds,
             Player::update_attack_state,
         )
@@ -58,6 +75,20 @@ pub(super) fn plugin(app: &mut App) {
 #[derive(Component, Reflect, Default)]
 struct PlayerShadow;
 
+type ShadowDecal = ForwardDecalMaterial<StandardMaterial>;
+type ShadowCaster<'w, 's> =
+    Single<'w, 's, (Entity, &'static Transform), (With<Player>, Without<PlayerShadow>)>;
+type Shadow<'w, 's> = Single<
+    'w,
+    's,
+    (
+        &'static mut Transform,
+        &'static mut Visibility,
+        &'static MeshMaterial3d<ShadowDecal>,
+    ),
+    With<PlayerShadow>,
+>;
+
 #[derive(Component, Reflect, Default)]
 struct PlayerModel {
     graph: Handle<AnimationGraph>,
@@ -70,6 +101,10 @@ struct PlayerModel {
     fall: AnimationNodeIndex,
     spin: AnimationNodeIndex,
     slam: AnimationNodeIndex,
+    swim: AnimationNodeIndex,
+    tread: AnimationNodeIndex,
+    hang: AnimationNodeIndex,
+    climb: AnimationNodeIndex,
     aplayer: Option<Entity>,
 }
 
@@ -108,12 +143,14 @@ impl Player {
                     Transform::from_xyz(0.0, -PLAYER_HEIGHT / 2.0, 0.0),
                     observers![Self::setup_animations],
                 ),
-                // (
-                //     ForwardDecal,
-                //     PlayerShadow,
-                //     Transform::from_scale(Vec3::splat(PLAYER_WIDTH)),
-                //     Make(Self::make_shadow),
-                // ),
+                Rope::bundle(),
+                (
+                    ForwardDecal,
+                    PlayerShadow,
+                    Transform::from_scale(Vec3::splat(SHADOW_SIZE)),
+                    Visibility::Hidden,
+                    Make(Self::make_shadow),
+                ),
             ],
         )
     }
@@ -163,27 +200,34 @@ impl Player {
         model.jump = load(2);
         model.spin = load(8);
         model.slam = load(4);
+        // Swimming reuses the run and idle clips, played back slower
+        model.swim = load(3);
+        model.tread = load(1);
+        // Ledges reuse the fall and jump clips
+        model.hang = load(0);
+        model.climb = load(2);
 
         model.graph = graphs.add(graph);
 
         Ok((SceneRoot(assets.load("player.glb#Scene0")), model))
     }
 
-    // fn make_shadow(
-    //     mut decals: ResMut<Assets<ForwardDecalMaterial<StandardMaterial>>>,
-    //     assets: Res<AssetServer>,
-    // ) -> Result<impl Bundle + use<>> {
-    //     Ok(MeshMaterial3d(decals.add(ForwardDecalMaterial {
-    //         base: StandardMaterial {
-    //             // base_color: Color::linear_rgb(0.1, 0.3, 0.8),
-    //             base_color_texture: Some(assets.load("shadow.png")),
-    //             ..default()
-    //         },
-    //         extension: ForwardDecalMaterialExt {
-    //             depth_fade_factor: 1.0,
-    //         },
-    //     })))
-    // }
+    fn make_shadow(
+        mut decals: ResMut<Assets<ForwardDecalMaterial<StandardMaterial>>>,
+        assets: Res<AssetServer>,
+    ) -> Result<impl Bundle + use<>> {
+        Ok(MeshMaterial3d(decals.add(ForwardDecalMaterial {
+            base: StandardMaterial {
+                base_color_texture: Some(assets.load("shadow.png")),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            },
+            extension: ForwardDecalMaterialExt {
+                depth_fade_factor: 1.0,
+            },
+        })))
+    }
 
     fn setup_animations(
         event: On<SceneInstanceReady>,
@@ -255,7 +299,31 @@ impl Player {
                 aplayer.stop_all();
                 aplayer.play(model.spin);
             }
-        } else if matches!(player.1, PlayerState::Air(_)) {
+        } else if let PlayerState::LedgeHang(lstate) = player.1 {
+            if lstate.climbing() {
+                if !aplayer.is_playing_animation(model.climb) {
+                    aplayer.stop_all();
+                    aplayer.play(model.climb).set_speed(2.0);
+                }
+            } else {
+                if !aplayer.is_playing_animation(model.hang) {
+                    aplayer.stop_all();
+                    aplayer.play(model.hang).set_speed(0.0);
+                }
+            }
+        } else if matches!(player.1, PlayerState::Swim(_)) {
+            if player.2.length_squared() > 1.0 {
+                if !aplayer.is_playing_animation(model.swim) {
+                    aplayer.stop_all();
+                    aplayer.play(model.swim).set_speed(0.6).repeat();
+                }
+            } else {
+                if !aplayer.is_playing_animation(model.tread) {
+                    aplayer.stop_all();
+                    aplayer.play(model.tread).set_speed(0.5).repeat();
+                }
+            }
+        } else if matches!(player.1, PlayerState::Air(_) | PlayerState::Swing(_)) {
             if player.2.y > 0.0 {
                 if !aplayer.is_playing_animation(model.jump) {
                     aplayer.stop_all();
@@ -311,35 +379,47 @@ impl Player {
         Ok(())
     }
 
-    // fn update_shadow(
-    //     player: Single<(Entity, &PlayerController, &Transform, &Collider), Changed<Transform>>,
-    //     mut shadow: Single<
-    //         (&mut Transform, &mut Visibility),
-    //         (With<PlayerShadow>, Without<PlayerController>),
-    //     >,
-    //     spatial: SpatialQuery,
-    // ) {
-    //     let hit = spatial.cast_shape(
-    //         &player.3,
-    //         player.2.translation,
-    //         Quat::default(),
-    //         Dir3::NEG_Y,
-    //         &ShapeCastConfig::default(),
-    //         &SpatialQueryFilter::from_excluded_entities([player.0]),
-    //     );
-
-    //     match hit {
-    //         None => {
-    //             *shadow.1 = Visibility::Hidden;
-    //         }
-    //         Some(hit) => {
-    //             *shadow.1 = Visibility::Visible;
-
-    //             shadow.0.translation.y =
-    //                 -hit.distance - player.3.shape().as_cuboid().unwrap().half_extents.y;
-    //         }
-    //     }
-    // }
+    fn update_shadow(
+        player: ShadowCaster,
+        mut shadow: Shadow,
+        mut decals: ResMut<Assets<ShadowDecal>>,
+        spatial: SpatialQuery,
+    ) {
+        let (entity, transform) = *player;
+
+        let hit = spatial.cast_ray(
+            transform.translation,
+            Dir3::NEG_Y,
+            SHADOW_MAX_DISTANCE + PLAYER_HEIGHT / 2.0,
+            true,
+            &SpatialQueryFilter::from_excluded_entities([entity]).with_mask(GameLayer::Level),
+        );
+
+        let Some(hit) = hit else {
+            *shadow.1 = Visibility::Hidden;
+            return;
+        };
+
+        *shadow.1 = Visibility::Inherited;
+
+        // The shadow is a child of the player, which never rotates
+        let height = (hit.distance - PLAYER_HEIGHT / 2.0).max(0.0);
+        let t = height / SHADOW_MAX_DISTANCE;
+
+        shadow.0.translation = Vec3::NEG_Y * hit.distance;
+        shadow.0.rotation = Quat::from_rotation_arc(Vec3::Y, hit.normal);
+        shadow.0.scale = Vec3::splat(SHADOW_SIZE * (1.0 - 0.6 * t));
+
+        let alpha = ((SHADOW_MAX_DISTANCE - height) / SHADOW_FADE_DISTANCE).clamp(0.0, 1.0);
+        // Only touch the material when it changes, to avoid reuploading it every frame
+        if decals
+            .get(&shadow.2.0)
+            .is_some_and(|decal| decal.base.base_color.alpha() != alpha)
+            && let Some(decal) = decals.get_mut(&shadow.2.0)
+        {
+            decal.base.base_color.set_alpha(alpha);
+        }
+    }
 
     fn play_sounds(
         player: Single<&Transform, With<Player>>,
@@ -383,6 +463,7 @@ impl Player {
             &PlayerControllerSettings,
             &mut PlayerState,
         )>,
+        mut msg: MessageWriter<PlayerControllerMessage>,
     ) {
         if player.0.attack_state == AttackState::Spin
             && player.1.y < player.2.min_sword_bounce
@@ -393,6 +474,7 @@ impl Player {
             state.jump_state = JumpState::None;
 
             player.1.y = (-player.1.y).max(player.2.min_sword_bounce);
+            msg.write(PlayerControllerMessage::Pogo);
         }
     }
 
@@ -0,0 +1,367 @@
+use std::f32::consts::TAU;
+
+use bevy::{light::NotShadowCaster, prelude::*};
+
+use crate::GameState;
+
+use super::{
+    PLAYER_HEIGHT, Player,
+    controller::{PlayerControllerMessage, PlayerState},
+};
+
+const POOL_SIZE: usize = 256;
+
+const DASH_TRAIL_TIME: f32 = 0.3;
+const TRAIL_INTERVAL: f32 = 0.02;
+const SPARK_INTERVAL: f32 = 0.03;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ParticlePool>()
+        .add_systems(
+            Update,
+            (ParticlePool::on_message, ParticlePool::emit_continuous)
+                .run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(
+            Update,
+            Particle::update.run_if(not(in_state(GameState::Paused))),
+        );
+}
+
+#[derive(Clone, Copy)]
+enum ParticleKind {
+    Dust,
+    Cloud,
+    Trail,
+    Spark,
+    Ring,
+}
+
+impl ParticleKind {
+    const ALL: [Self; 5] = [
+        Self::Dust,
+        Self::Cloud,
+        Self::Trail,
+        Self::Spark,
+        Self::Ring,
+    ];
+
+    fn material(self) -> StandardMaterial {
+        match self {
+            Self::Dust => StandardMaterial {
+                base_color: Color::linear_rgb(0.6, 0.5, 0.4),
+                perceptual_roughness: 1.0,
+                ..default()
+            },
+            Self::Cloud => StandardMaterial {
+                base_color: Color::WHITE,
+                perceptual_roughness: 1.0,
+                ..default()
+            },
+            Self::Trail => StandardMaterial {
+                base_color: Color::linear_rgb(0.2, 0.5, 1.0),
+                emissive: LinearRgba::rgb(0.4, 1.0, 2.0),
+                unlit: true,
+                ..default()
+            },
+            Self::Spark => StandardMaterial {
+                base_color: Color::linear_rgb(1.0, 0.6, 0.1),
+                emissive: LinearRgba::rgb(4.0, 2.0, 0.4),
+                unlit: true,
+                ..default()
+            },
+            Self::Ring => StandardMaterial {
+                base_color: Color::linear_rgb(1.0, 0.9, 0.8),
+                unlit: true,
+                ..default()
+            },
+        }
+    }
+}
+
+#[derive(Component, Default)]
+struct Particle {
+    velocity: Vec3,
+    gravity: f32,
+    drag: f32,
+    size: f32,
+    age: f32,
+    lifetime: f32,
+}
+
+impl Particle {
+    fn update(q: Query<(&mut Particle, &mut Transform, &mut Visibility)>, time: Res<Time>) {
+        let dt = time.delta_secs();
+
+        for (mut particle, mut transform, mut vis) in q {
+            if *vis == Visibility::Hidden {
+                continue;
+            }
+
+            particle.age += dt;
+            if particle.age >= particle.lifetime {
+                *vis = Visibility::Hidden;
+                continue;
+            }
+
+            let drag = f32::exp(-particle.drag * dt);
+            particle.velocity.y -= particle.gravity * dt;
+            particle.velocity *= drag;
+
+            transform.translation += particle.velocity * dt;
+
+            // Shrink away instead of fading, so every particle of a kind can share a material
+            let life = 1.0 - particle.age / particle.lifetime;
+            transform.scale = Vec3::splat(particle.size * life);
+        }
+    }
+}
+
+/// Particles are spawned once and recycled, oldest first
+#[derive(Resource)]
+struct ParticlePool {
+    particles: Vec<Entity>,
+    next: usize,
+    materials: Vec<Handle<StandardMaterial>>,
+    dash_trail: f32,
+}
+
+impl FromWorld for ParticlePool {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Sphere::new(1.0).mesh().ico(1).unwrap());
+
+        let materials = ParticleKind::ALL
+            .iter()
+            .map(|kind| {
+                world
+                    .resource_mut::<Assets<StandardMaterial>>()
+                    .add(kind.material())
+            })
+            .collect::<Vec<_>>();
+
+        let particles = (0..POOL_SIZE)
+            .map(|_| {
+                world
+                    .spawn((
+                        Name::new("Particle"),
+                        Particle::default(),
+                        Mesh3d(mesh.clone()),
+                        MeshMaterial3d(materials[0].clone()),
+                        Transform::default(),
+                        Visibility::Hidden,
+                        NotShadowCaster,
+                    ))
+                    .id()
+            })
+            .collect();
+
+        Self {
+            particles,
+            next: 0,
+            materials,
+            dash_trail: 0.0,
+        }
+    }
+}
+
+type ParticleQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Particle,
+        &'static mut Transform,
+        &'static mut Visibility,
+        &'static mut MeshMaterial3d<StandardMaterial>,
+    ),
+    Without<Player>,
+>;
+
+impl ParticlePool {
+    fn emit(
+        &mut self,
+        q: &mut ParticleQuery,
+        kind: ParticleKind,
+        position: Vec3,
+        particle: Particle,
+    ) {
+        let entity = self.particles[self.next];
+        self.next = (self.next + 1) % self.particles.len();
+
+        let Ok((mut p, mut transform, mut vis, mut material)) = q.get_mut(entity) else {
+            return;
+        };
+
+        transform.translation = position;
+        transform.scale = Vec3::splat(particle.size);
+        *p = particle;
+        *vis = Visibility::Visible;
+        material.0 = self.materials[kind as usize].clone();
+    }
+
+    /// Emits `count` particles spread evenly in a horizontal circle, with `speed` as (outward, up)
+    fn burst(
+        &mut self,
+        q: &mut ParticleQuery,
+        kind: ParticleKind,
+        position: Vec3,
+        count: usize,
+        speed: Vec2,
+        particle: Particle,
+    ) {
+        for i in 0..count {
+            let angle = i as f32 / count as f32 * TAU;
+            let dir = vec3(angle.cos(), 0.0, angle.sin());
+
+            self.emit(
+                q,
+                kind,
+                position + dir * 0.2,
+                Particle {
+                    velocity: dir * speed.x + Vec3::Y * speed.y,
+                    ..particle
+                },
+            );
+        }
+    }
+
+    fn on_message(
+        mut pool: ResMut<ParticlePool>,
+        mut q: ParticleQuery,
+        mut msg: MessageReader<PlayerControllerMessage>,
+        player: Single<&Transform, With<Player>>,
+    ) {
+        let feet = player.translation - Vec3::Y * (PLAYER_HEIGHT / 2.0);
+
+        for msg in msg.read() {
+            match msg {
+                PlayerControllerMessage::GroundJump
+                | PlayerControllerMessage::CoyoteTimeJump
+                | PlayerControllerMessage::CoyoteFrictionJump
+                | PlayerControllerMessage::Land => {
+                    pool.burst(
+                        &mut q,
+                        ParticleKind::Dust,
+                        feet,
+                        8,
+                        vec2(2.0, 0.5),
+                        Particle {
+                            drag: 4.0,
+                            size: 0.12,
+                            lifetime: 0.4,
+                            ..default()
+                        },
+                    );
+                }
+                PlayerControllerMessage::AirJump => {
+                    pool.burst(
+                        &mut q,
+                        ParticleKind::Cloud,
+                        feet,
+                        10,
+                        vec2(3.0, -1.0),
+                        Particle {
+                            drag: 5.0,
+                            size: 0.18,
+                            lifetime: 0.5,
+                            ..default()
+                        },
+                    );
+                }
+                PlayerControllerMessage::Slam(point) => {
+                    pool.burst(
+                        &mut q,
+                        ParticleKind::Ring,
+                        *point,
+                        24,
+                        vec2(8.0, 0.0),
+                        Particle {
+                            drag: 3.0,
+                            size: 0.15,
+                            lifetime: 0.5,
+                            ..default()
+                        },
+                    );
+                }
+                PlayerControllerMessage::Dash => {
+                    pool.dash_trail = DASH_TRAIL_TIME;
+                }
+                PlayerControllerMessage::Slide => {
+                    pool.burst(
+                        &mut q,
+                        ParticleKind::Spark,
+                        feet,
+                        12,
+                        vec2(3.0, 2.0),
+                        Particle {
+                            gravity: 15.0,
+                            drag: 2.0,
+                            size: 0.06,
+                            lifetime: 0.4,
+                            ..default()
+                        },
+                    );
+                }
+                PlayerControllerMessage::WallGrab | PlayerControllerMessage::Pogo => {}
+            }
+        }
+    }
+
+    fn emit_continuous(
+        mut pool: ResMut<ParticlePool>,
+        mut q: ParticleQuery,
+        player: Single<(&Transform, &PlayerState), With<Player>>,
+        mut trail_timer: Local<f32>,
+        mut spark_timer: Local<f32>,
+        mut count: Local<u32>,
+        time: Res<Time>,
+    ) {
+        let dt = time.delta_secs();
+        let position = player.0.translation;
+
+        pool.dash_trail = (pool.dash_trail - dt).max(0.0);
+
+        if pool.dash_trail > 0.0 {
+            *trail_timer += dt;
+            while *trail_timer >= TRAIL_INTERVAL {
+                *trail_timer -= TRAIL_INTERVAL;
+                pool.emit(
+                    &mut q,
+                    ParticleKind::Trail,
+                    position,
+                    Particle {
+                        size: 0.2,
+                        lifetime: 0.25,
+                        ..default()
+                    },
+                );
+            }
+        }
+
+        if matches!(player.1, PlayerState::Sliding(_)) {
+            *spark_timer += dt;
+            while *spark_timer >= SPARK_INTERVAL {
+                *spark_timer -= SPARK_INTERVAL;
+                *count += 1;
+
+                // Golden angle, so consecutive sparks spread out
+                let angle = *count as f32 * 2.4;
+                let dir = vec3(angle.cos(), 0.0, angle.sin());
+
+                pool.emit(
+                    &mut q,
+                    ParticleKind::Spark,
+                    position - Vec3::Y * (PLAYER_HEIGHT / 4.0),
+                    Particle {
+                        velocity: dir * 1.5 + Vec3::Y * 2.5,
+                        gravity: 15.0,
+                        size: 0.05,
+                        lifetime: 0.35,
+                        ..default()
+                    },
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use avian3d::prelude::*;
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use crate::GameState;
+
+use super::controller::{PlayerControllerMessage, PlayerControllerSettings};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        release_shockwave.run_if(in_state(GameState::InGame)),
+    );
+}
+
+/// Something that reacts to the shockwave of a slam
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct SlamTarget;
+
+/// Triggered on every [`SlamTarget`] within the radius of a slam
+#[derive(EntityEvent, Clone)]
+pub struct SlamHit {
+    pub entity: Entity,
+}
+
+fn release_shockwave(
+    mut msg: MessageReader<PlayerControllerMessage>,
+    settings: Single<&PlayerControllerSettings>,
+    targets: Query<(), With<SlamTarget>>,
+    parents: Query<&ChildOf>,
+    spatial: SpatialQuery,
+    mut cmd: Commands,
+) {
+    for msg in msg.read() {
+        let PlayerControllerMessage::Slam(origin) = *msg else {
+            continue;
+        };
+
+        // Colliders are often children of the target, so look through their ancestors
+        let hit = spatial
+            .shape_intersections(
+                &Collider::sphere(settings.slam_shockwave_radius),
+                origin,
+                Quat::default(),
+                &SpatialQueryFilter::default(),
+            )
+            .into_iter()
+            .filter_map(|entity| {
+                std::iter::once(entity)
+                    .chain(parents.iter_ancestors(entity))
+                    .find(|&e| targets.contains(e))
+            })
+            .collect::<HashSet<Entity>>();
+
+        for entity in hit {
+            cmd.trigger(SlamHit { entity });
+        }
+    }
+}
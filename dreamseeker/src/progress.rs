@@ -0,0 +1,320 @@
+use avian3d::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*, transform::TransformSystems};
+
+use crate::{
+    collision::GameLayer,
+    player::{
+        Player,
+        item::{Chest, Token},
+    },
+    save::SaveData,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Progress>()
+        .add_systems(
+            PostUpdate,
+            Progress::register.after(TransformSystems::Propagate),
+        )
+        .add_systems(Update, Progress::explore);
+}
+
+/// A named part of the level whose collectibles are tracked together.
+/// Contains everything under it in the scene, or everything inside its collider
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component, Default)]
+#[require(
+    Transform,
+    Sensor,
+    CollisionLayers::new(GameLayer::Sensor, LayerMask::NONE)
+)]
+pub struct Area {
+    pub id: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollectibleKind {
+    Token,
+    Chest,
+}
+
+impl CollectibleKind {
+    /// Start of the ids of this kind of collectible
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::Token => "token@",
+            Self::Chest => "chest@",
+        }
+    }
+
+    /// Id of a collectible of this kind placed at `position`, to a tenth of a meter
+    pub fn id(self, position: Vec3) -> String {
+        // Adding zero turns -0.0 into 0.0, so tiny offsets around zero give the same id
+        let Vec3 { x, y, z } = (position * 10.0).round() / 10.0 + 0.0;
+        format!("{}{x:.1},{y:.1},{z:.1}", self.prefix())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Collectible {
+    pub kind: CollectibleKind,
+    pub area: Option<String>,
+    pub position: Vec3,
+}
+
+/// Identifies a collectible across sessions, from where it was placed in the level
+#[derive(Component, Clone, Debug)]
+pub struct CollectibleId(pub String);
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Count {
+    pub found: usize,
+    pub total: usize,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct AreaProgress {
+    /// `None` for collectibles outside of every area
+    pub id: Option<String>,
+    pub tokens: Count,
+    pub chests: Count,
+}
+
+/// Every collectible in the level, including the ones that were already found
+#[derive(Resource, Default)]
+pub struct Progress {
+    collectibles: HashMap<String, Collectible>,
+}
+
+type NewCollectibles<'w, 's> =
+    Query<'w, 's, (Entity, &'static GlobalTransform, Has<Token>), Or<(Added<Token>, Added<Chest>)>>;
+
+impl Progress {
+    pub fn collectibles(&self) -> impl Iterator<Item = (&String, &Collectible)> {
+        self.collectibles.iter()
+    }
+
+    /// Adds a collectible placed at `position`, returning its id
+    pub fn insert(
+        &mut self,
+        kind: CollectibleKind,
+        area: Option<String>,
+        position: Vec3,
+    ) -> String {
+        let id = kind.id(position);
+        self.collectibles.insert(
+            id.clone(),
+            Collectible {
+                kind,
+                area,
+                position,
+            },
+        );
+        id
+    }
+
+    /// Whether the player has been in `area`, or found anything in it
+    pub fn explored(&self, area: &str, save: &SaveData) -> bool {
+        save.explored.contains(area)
+            || self
+                .collectibles
+                .iter()
+                .any(|(id, c)| c.area.as_deref() == Some(area) && save.found.contains(id))
+    }
+
+    /// Progress of each area, sorted by id
+    pub fn areas(&self, save: &SaveData) -> Vec<AreaProgress> {
+        let mut areas = HashMap::<Option<String>, AreaProgress>::default();
+
+        for (id, Collectible { kind, area, .. }) in &self.collectibles {
+            let progress = areas.entry(area.clone()).or_insert_with(|| AreaProgress {
+                id: area.clone(),
+                ..default()
+            });
+            let count = match kind {
+                CollectibleKind::Token => &mut progress.tokens,
+                CollectibleKind::Chest => &mut progress.chests,
+            };
+
+            count.total += 1;
+            if save.found.contains(id) {
+                count.found += 1;
+            }
+        }
+
+        let mut areas = areas.into_values().collect::<Vec<_>>();
+        areas.sort_by(|a, b| a.id.cmp(&b.id));
+        areas
+    }
+
+    pub fn count(&self, kind: CollectibleKind, save: &SaveData) -> Count {
+        self.collectibles
+            .iter()
+            .filter(|(_, c)| c.kind == kind)
+            .fold(Count::default(), |count, (id, _)| Count {
+                found: count.found + save.found.contains(id) as usize,
+                total: count.total + 1,
+            })
+    }
+
+    /// Percentage of every collectible found
+    pub fn completion(&self, save: &SaveData) -> f32 {
+        if self.collectibles.is_empty() {
+            return 0.0;
+        }
+
+        let found = self
+            .collectibles
+            .keys()
+            .filter(|id| save.found.contains(*id))
+            .count();
+
+        100.0 * found as f32 / self.collectibles.len() as f32
+    }
+
+    fn register(
+        new: NewCollectibles,
+        parents: Query<&ChildOf>,
+        areas: Query<(&Area, &GlobalTransform, Option<&Collider>)>,
+        mut progress: ResMut<Progress>,
+        save: Res<SaveData>,
+        mut cmd: Commands,
+    ) {
+        for (entity, transform, is_token) in new {
+            let kind = if is_token {
+                CollectibleKind::Token
+            } else {
+                CollectibleKind::Chest
+            };
+
+            let pos = transform.translation();
+
+            // Prefer the scene sub-tree, then any volume containing it
+            let area = parents
+                .iter_ancestors(entity)
+                .find_map(|e| areas.get(e).ok())
+                .or_else(|| {
+                    areas.iter().find(|(_, area_transform, collider)| {
+                        let (_, rotation, translation) =
+                            area_transform.to_scale_rotation_translation();
+                        collider.is_some_and(|c| c.contains_point(translation, rotation, pos))
+                    })
+                })
+                .map(|(area, _, _)| area.id.clone());
+
+            let id = progress.insert(kind, area, pos);
+
+            // Already found in an earlier session
+            if save.found.contains(&id) {
+                cmd.entity(entity).despawn();
+            } else {
+                cmd.entity(entity).insert(CollectibleId(id));
+            }
+        }
+    }
+
+    fn explore(
+        player: Single<&GlobalTransform, With<Player>>,
+        areas: Query<(&Area, &GlobalTransform, &Collider)>,
+        mut save: ResMut<SaveData>,
+    ) {
+        let pos = player.translation();
+
+        for (area, transform, collider) in areas {
+            let (_, rotation, translation) = transform.to_scale_rotation_translation();
+            if !save.explored.contains(&area.id)
+                && collider.contains_point(translation, rotation, pos)
+            {
+                save.explored.insert(area.id.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress() -> Progress {
+        let mut progress = Progress::default();
+        let forest = Some("forest".to_string());
+        progress.insert(CollectibleKind::Token, forest.clone(), vec3(1.0, 0.0, 0.0));
+        progress.insert(CollectibleKind::Token, forest, vec3(2.0, 0.0, 0.0));
+        progress.insert(
+            CollectibleKind::Chest,
+            Some("cave".into()),
+            vec3(3.0, 0.0, 0.0),
+        );
+        progress.insert(CollectibleKind::Token, None, vec3(4.0, 0.0, 0.0));
+        progress
+    }
+
+    fn save(found: &[(CollectibleKind, Vec3)]) -> SaveData {
+        SaveData {
+            found: found.iter().map(|&(kind, pos)| kind.id(pos)).collect(),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn ids_come_from_kind_and_position() {
+        assert_eq!(
+            CollectibleKind::Token.id(vec3(1.0, 2.5, -3.0)),
+            "token@1.0,2.5,-3.0"
+        );
+        assert_eq!(
+            CollectibleKind::Chest.id(vec3(1.0, 2.5, -3.0)),
+            "chest@1.0,2.5,-3.0"
+        );
+    }
+
+    #[test]
+    fn ids_ignore_float_noise() {
+        let kind = CollectibleKind::Token;
+
+        assert_eq!(
+            kind.id(vec3(1.0, 2.0, 3.0)),
+            kind.id(vec3(1.00001, 1.99998, 3.0))
+        );
+        assert_eq!(kind.id(Vec3::ZERO), kind.id(vec3(-0.01, -0.0001, 0.0)));
+        assert_ne!(kind.id(vec3(1.0, 2.0, 3.0)), kind.id(vec3(1.2, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn counts_by_kind() {
+        let progress = progress();
+        let save = save(&[(CollectibleKind::Token, vec3(2.0, 0.0, 0.0))]);
+
+        let tokens = progress.count(CollectibleKind::Token, &save);
+        assert_eq!((tokens.found, tokens.total), (1, 3));
+        let chests = progress.count(CollectibleKind::Chest, &save);
+        assert_eq!((chests.found, chests.total), (0, 1));
+        assert_eq!(progress.completion(&save), 25.0);
+    }
+
+    #[test]
+    fn empty_level_has_no_completion() {
+        assert_eq!(Progress::default().completion(&SaveData::default()), 0.0);
+    }
+
+    #[test]
+    fn areas_are_sorted_with_the_rest_first() {
+        let areas = progress().areas(&SaveData::default());
+        let ids = areas.iter().map(|a| a.id.as_deref()).collect::<Vec<_>>();
+
+        assert_eq!(ids, [None, Some("cave"), Some("forest")]);
+        assert_eq!(areas[2].tokens.total, 2);
+        assert_eq!(areas[1].chests.total, 1);
+    }
+
+    #[test]
+    fn finding_something_explores_its_area() {
+        let progress = progress();
+        let mut save = save(&[(CollectibleKind::Chest, vec3(3.0, 0.0, 0.0))]);
+        save.explored.insert("forest".into());
+
+        assert!(progress.explored("cave", &save));
+        assert!(progress.explored("forest", &save));
+        assert!(!progress.explored("mountain", &save));
+    }
+}
@@ -0,0 +1,369 @@
+use bevy::{
+    ecs::system::SystemParam,
+    input::{
+        gamepad::{GamepadAxisChangedEvent, GamepadButtonChangedEvent},
+        keyboard::KeyboardInput,
+        mouse::{MouseButtonInput, MouseMotion},
+    },
+    platform::collections::HashMap,
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+use crate::locale::Strings;
+
+const AXIS_THRESHOLD: f32 = 0.5;
+const MOUSE_THRESHOLD: f32 = 4.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<InputDevice>()
+        .init_resource::<Prompts>()
+        .add_systems(PreUpdate, InputDevice::detect.before(CollectPrompts));
+}
+
+/// Runs the systems that fill [`Prompts`] from the current bindings
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CollectPrompts;
+
+pub trait PromptAppExt {
+    /// Makes `{input:<name>}` in text show the bindings of `A`
+    fn register_prompt<A: InputAction>(&mut self, name: &'static str) -> &mut Self;
+}
+
+impl PromptAppExt for App {
+    fn register_prompt<A: InputAction>(&mut self, name: &'static str) -> &mut Self {
+        self.add_systems(
+            PreUpdate,
+            Prompts::collect::<A>(name).in_set(CollectPrompts),
+        )
+    }
+}
+
+/// The device the player used last, prompts show bindings for it
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputDevice {
+    #[default]
+    KeyboardMouse,
+    Gamepad(GamepadStyle),
+}
+
+/// Which face button labels a gamepad uses
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GamepadStyle {
+    #[default]
+    Xbox,
+    PlayStation,
+    Nintendo,
+}
+
+impl GamepadStyle {
+    fn from_vendor(vendor: Option<u16>) -> Self {
+        match vendor {
+            Some(0x054C) => Self::PlayStation,
+            Some(0x057E) => Self::Nintendo,
+            _ => Self::Xbox,
+        }
+    }
+}
+
+impl InputDevice {
+    fn detect(
+        mut device: ResMut<InputDevice>,
+        mut keys: MessageReader<KeyboardInput>,
+        mut mouse_buttons: MessageReader<MouseButtonInput>,
+        mut mouse_motion: MessageReader<MouseMotion>,
+        mut buttons: MessageReader<GamepadButtonChangedEvent>,
+        mut axes: MessageReader<GamepadAxisChangedEvent>,
+        gamepads: Query<&Gamepad>,
+    ) {
+        let mouse_moved =
+            mouse_motion.read().map(|m| m.delta.length()).sum::<f32>() > MOUSE_THRESHOLD;
+        let keyboard = keys.read().count() > 0 || mouse_buttons.read().count() > 0 || mouse_moved;
+
+        let gamepad = buttons
+            .read()
+            .filter(|b| b.value > AXIS_THRESHOLD)
+            .map(|b| b.entity)
+            .chain(
+                axes.read()
+                    .filter(|a| a.value.abs() > AXIS_THRESHOLD)
+                    .map(|a| a.entity),
+            )
+            .last();
+
+        let new = match gamepad {
+            Some(entity) => InputDevice::Gamepad(GamepadStyle::from_vendor(
+                gamepads.get(entity).ok().and_then(Gamepad::vendor_id),
+            )),
+            None if keyboard => InputDevice::KeyboardMouse,
+            None => return,
+        };
+
+        device.set_if_neq(new);
+    }
+}
+
+/// The bindings of the action `A`
+#[derive(SystemParam)]
+struct ActionBindings<'w, 's, A: InputAction> {
+    actions: Query<'w, 's, &'static Bindings, With<Action<A>>>,
+    bindings: Query<'w, 's, &'static Binding>,
+}
+
+/// Labels of the bindings of each registered action, for the current [`InputDevice`]
+#[derive(Resource, Default)]
+pub struct Prompts {
+    labels: HashMap<&'static str, String>,
+}
+
+impl Prompts {
+    /// Replaces every `{input:<name>}` in `text` with the bindings of that action
+    pub fn fill(&self, text: String) -> String {
+        if !text.contains("{input:") {
+            return text;
+        }
+
+        self.labels.iter().fold(text, |text, (name, label)| {
+            text.replace(&format!("{{input:{name}}}"), label)
+        })
+    }
+
+    fn collect<A: InputAction>(
+        name: &'static str,
+    ) -> impl FnMut(ResMut<Prompts>, Res<InputDevice>, Res<Strings>, ActionBindings<A>) {
+        move |mut prompts, device, strings, action| {
+            // Keep the last known label while the context isn't spawned
+            let Some(bindings) = action.actions.iter().next() else {
+                return;
+            };
+
+            let mut labels = Vec::new();
+            for binding in action.bindings.iter_many(bindings.iter()) {
+                if let Some(label) = binding_label(binding, *device, &strings)
+                    && !labels.contains(&label)
+                {
+                    labels.push(label);
+                }
+            }
+            let label = labels.join("/");
+
+            if prompts.labels.get(name) != Some(&label) {
+                prompts.labels.insert(name, label);
+            }
+        }
+    }
+}
+
+/// The name of the binding on `device`, which can be translated, like "Space" or "Left Stick"
+fn binding_label(binding: &Binding, device: InputDevice, strings: &Strings) -> Option<String> {
+    match (binding, device) {
+        (Binding::Keyboard { key, .. }, InputDevice::KeyboardMouse) => {
+            Some(key_label(*key, strings))
+        }
+        (Binding::MouseButton { button, .. }, InputDevice::KeyboardMouse) => {
+            Some(mouse_label(*button, strings))
+        }
+        (Binding::MouseMotion { .. }, InputDevice::KeyboardMouse) => {
+            Some(strings.get("input.mouse"))
+        }
+        (Binding::MouseWheel { .. }, InputDevice::KeyboardMouse) => {
+            Some(strings.get("input.wheel"))
+        }
+        (Binding::GamepadButton(button), InputDevice::Gamepad(style)) => {
+            Some(button_label(*button, style, strings))
+        }
+        (Binding::GamepadAxis(axis), InputDevice::Gamepad(style)) => {
+            axis_label(*axis, style, strings)
+        }
+        _ => None,
+    }
+}
+
+fn key_label(key: KeyCode, strings: &Strings) -> String {
+    let key = match key {
+        KeyCode::Space => "input.space",
+        KeyCode::Escape => "input.escape",
+        KeyCode::Enter => "input.enter",
+        KeyCode::Tab => "input.tab",
+        KeyCode::Backspace => "input.backspace",
+        KeyCode::ShiftLeft | KeyCode::ShiftRight => "input.shift",
+        KeyCode::ControlLeft | KeyCode::ControlRight => "input.ctrl",
+        KeyCode::AltLeft | KeyCode::AltRight => "input.alt",
+        KeyCode::ArrowUp => return "↑".into(),
+        KeyCode::ArrowDown => return "↓".into(),
+        KeyCode::ArrowLeft => return "←".into(),
+        KeyCode::ArrowRight => return "→".into(),
+        _ => {
+            let name = format!("{key:?}");
+            return name
+                .strip_prefix("Key")
+                .or_else(|| name.strip_prefix("Digit"))
+                .unwrap_or(&name)
+                .to_string();
+        }
+    };
+
+    strings.get(key)
+}
+
+fn mouse_label(button: MouseButton, strings: &Strings) -> String {
+    match button {
+        MouseButton::Left => strings.get("input.left_click"),
+        MouseButton::Right => strings.get("input.right_click"),
+        MouseButton::Middle => strings.get("input.middle_click"),
+        button => strings.format(
+            "input.mouse_button",
+            &[("button".into(), format!("{button:?}"))],
+        ),
+    }
+}
+
+fn button_label(button: GamepadButton, style: GamepadStyle, strings: &Strings) -> String {
+    use GamepadButton as B;
+
+    let key = match button {
+        B::LeftThumb => "input.left_stick",
+        B::RightThumb => "input.right_stick",
+        B::DPadUp => "input.dpad_up",
+        B::DPadDown => "input.dpad_down",
+        B::DPadLeft => "input.dpad_left",
+        B::DPadRight => "input.dpad_right",
+        _ => return button_glyph(button, style).into(),
+    };
+
+    strings.get(key)
+}
+
+/// The label printed on the button, which is never translated
+fn button_glyph(button: GamepadButton, style: GamepadStyle) -> &'static str {
+    use GamepadButton as B;
+    use GamepadStyle as S;
+
+    match (button, style) {
+        (B::South, S::Xbox) => "A",
+        (B::East, S::Xbox) => "B",
+        (B::West, S::Xbox) => "X",
+        (B::North, S::Xbox) => "Y",
+        (B::South, S::PlayStation) => "✕",
+        (B::East, S::PlayStation) => "○",
+        (B::West, S::PlayStation) => "□",
+        (B::North, S::PlayStation) => "△",
+        (B::South, S::Nintendo) => "B",
+        (B::East, S::Nintendo) => "A",
+        (B::West, S::Nintendo) => "Y",
+        (B::North, S::Nintendo) => "X",
+        (B::LeftTrigger, S::Xbox) => "LB",
+        (B::RightTrigger, S::Xbox) => "RB",
+        (B::LeftTrigger2, S::Xbox) => "LT",
+        (B::RightTrigger2, S::Xbox) => "RT",
+        (B::LeftTrigger, S::PlayStation) => "L1",
+        (B::RightTrigger, S::PlayStation) => "R1",
+        (B::LeftTrigger2, S::PlayStation) => "L2",
+        (B::RightTrigger2, S::PlayStation) => "R2",
+        (B::LeftTrigger, S::Nintendo) => "L",
+        (B::RightTrigger, S::Nintendo) => "R",
+        (B::LeftTrigger2, S::Nintendo) => "ZL",
+        (B::RightTrigger2, S::Nintendo) => "ZR",
+        (B::Select, S::Xbox) => "View",
+        (B::Start, S::Xbox) => "Menu",
+        (B::Select, S::PlayStation) => "Share",
+        (B::Start, S::PlayStation) => "Options",
+        (B::Select, S::Nintendo) => "-",
+        (B::Start, S::Nintendo) => "+",
+        _ => "?",
+    }
+}
+
+fn axis_label(axis: GamepadAxis, style: GamepadStyle, strings: &Strings) -> Option<String> {
+    let button = match axis {
+        GamepadAxis::LeftStickX | GamepadAxis::LeftStickY => GamepadButton::LeftThumb,
+        GamepadAxis::RightStickX | GamepadAxis::RightStickY => GamepadButton::RightThumb,
+        GamepadAxis::LeftZ => GamepadButton::LeftTrigger2,
+        GamepadAxis::RightZ => GamepadButton::RightTrigger2,
+        _ => return None,
+    };
+
+    Some(button_label(button, style, strings))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::{
+        input::ui::{self, Confirm},
+        locale::StringTable,
+    };
+
+    use super::*;
+
+    fn english() -> Strings {
+        let table: StringTable =
+            ron::from_str(include_str!("../assets/locale/en.strings.ron")).unwrap();
+        Strings::merge(Some(&table), None, false)
+    }
+
+    fn confirm_prompt(device: InputDevice) -> String {
+        let mut world = World::new();
+        world.insert_resource(device);
+        world.insert_resource(english());
+        world.init_resource::<Prompts>();
+        world.spawn(ui::actions());
+
+        world
+            .run_system_once(Prompts::collect::<Confirm>("confirm"))
+            .unwrap();
+        world.resource::<Prompts>().fill("{input:confirm}".into())
+    }
+
+    #[test]
+    fn confirm_shows_its_bindings_for_each_device() {
+        use GamepadStyle as S;
+
+        assert_eq!(confirm_prompt(InputDevice::KeyboardMouse), "Space");
+        assert_eq!(confirm_prompt(InputDevice::Gamepad(S::Xbox)), "B");
+        assert_eq!(confirm_prompt(InputDevice::Gamepad(S::PlayStation)), "○");
+        assert_eq!(confirm_prompt(InputDevice::Gamepad(S::Nintendo)), "A");
+    }
+
+    #[test]
+    fn keys_drop_their_prefix() {
+        let strings = english();
+
+        assert_eq!(key_label(KeyCode::KeyW, &strings), "W");
+        assert_eq!(key_label(KeyCode::Digit1, &strings), "1");
+        assert_eq!(key_label(KeyCode::ControlLeft, &strings), "Ctrl");
+        assert_eq!(key_label(KeyCode::ArrowUp, &strings), "↑");
+    }
+
+    #[test]
+    fn labels_only_show_for_their_device() {
+        let strings = english();
+        let key = Binding::from(KeyCode::Space);
+        let stick = Binding::from(GamepadAxis::LeftStickX);
+        let gamepad = InputDevice::Gamepad(GamepadStyle::Xbox);
+
+        assert_eq!(binding_label(&key, gamepad, &strings), None);
+        assert_eq!(
+            binding_label(&stick, gamepad, &strings).as_deref(),
+            Some("Left Stick")
+        );
+        assert_eq!(
+            binding_label(&stick, InputDevice::KeyboardMouse, &strings),
+            None
+        );
+    }
+
+    #[test]
+    fn fill_replaces_every_prompt() {
+        let prompts = Prompts {
+            labels: [("jump", "Space".into()), ("dash", "Shift".into())].into(),
+        };
+
+        assert_eq!(
+            prompts.fill("{input:jump}, {input:dash}, {input:jump}".into()),
+            "Space, Shift, Space"
+        );
+        assert_eq!(prompts.fill("{input:other}".into()), "{input:other}");
+    }
+}
@@ -0,0 +1,374 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, File},
+    io::{self, Write},
+    sync::Mutex,
+};
+
+use bevy::{
+    prelude::*,
+    tasks::{IoTaskPool, Task, block_on},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GameState,
+    achievement::PlayEvent,
+    assist::Assists,
+    dialogue::DialogueFlags,
+    locale::{Language, Locale, LocalizedText},
+    player::{
+        Player,
+        camera::PlayerCamera,
+        item::{Item, PlayerItems},
+    },
+    progress::CollectibleKind,
+    trigger::Checkpoint,
+    ui::toast::{Toast, ToastArea},
+};
+
+const SAVE_PATH: &str = "save.ron";
+/// Written first and renamed over `save.ron`, so a crash mid-write can't truncate the save
+const TEMP_PATH: &str = "save.ron.tmp";
+/// How long to wait after a change before writing, so bursts of changes are written once
+const WRITE_DELAY: f32 = 1.0;
+/// How often the play time and event counts are written, in seconds
+const AUTOSAVE_INTERVAL: f32 = 30.0;
+/// Moving further than this in one frame is a respawn or teleport, not travel
+const MAX_STEP: f32 = 5.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(SaveData::load())
+        .init_resource::<SaveWriter>()
+        .add_systems(
+            Startup,
+            (
+                SaveData::restore_dialogue_flags,
+                SaveData::restore_language,
+                SaveData::restore_assists,
+            ),
+        )
+        .add_systems(
+            Update,
+            (SaveData::count_play_time, SaveData::count_distance)
+                .run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                SaveData::restore_checkpoints,
+                SaveData::restore_player,
+                SaveData::restore_camera,
+                SaveData::record_items,
+                SaveData::record_checkpoints,
+                SaveData::record_last_checkpoint,
+                SaveData::record_camera,
+                SaveData::record_dialogue_flags,
+                SaveData::record_language,
+                SaveData::record_assists,
+                SaveWriter::schedule,
+                SaveWriter::finish,
+                SaveWriter::write_on_exit,
+            )
+                .chain(),
+        );
+}
+
+/// Everything that persists between sessions, written to `save.ron` shortly after it changes
+#[derive(Resource, Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct SaveData {
+    /// Ids of the collectibles the player found
+    pub found: BTreeSet<String>,
+    /// Ids of the areas the player has been in
+    pub explored: BTreeSet<String>,
+    pub items: Vec<Item>,
+    /// Ids of the activated checkpoints
+    pub checkpoints: BTreeSet<String>,
+    /// Id of the checkpoint the player respawns at
+    pub last_checkpoint: Option<String>,
+    /// Flags set by dialogue choices
+    pub dialogue_flags: BTreeSet<String>,
+    pub assists: Assists,
+    /// Set once any assist is turned on, for completion stats
+    pub assisted: bool,
+    /// Ids of the unlocked achievements
+    pub achievements: BTreeSet<String>,
+    /// How many times each event happened
+    pub events: BTreeMap<PlayEvent, u32>,
+    /// Seconds spent in game, not counting menus and cutscenes
+    pub play_time: f32,
+    /// Deaths at each death trigger, by its name or position
+    pub deaths: BTreeMap<String, u32>,
+    /// Meters the player moved
+    pub distance: f32,
+    /// Zoom with the camera stick instead of changing the pitch
+    pub stick_zoom: bool,
+    /// Turn the camera behind the player while running
+    pub auto_follow: bool,
+    pub language: Language,
+}
+
+impl SaveData {
+    /// Reads `save.ron`, or starts a new save if there is none
+    fn load() -> Self {
+        let Ok(text) = fs::read_to_string(SAVE_PATH) else {
+            return default();
+        };
+
+        ron::from_str(&text).unwrap_or_else(|e| {
+            warn!("Ignoring unreadable save file: {e}");
+            default()
+        })
+    }
+
+    pub fn count(&self, event: PlayEvent) -> u32 {
+        self.events.get(&event).copied().unwrap_or(0)
+    }
+
+    pub fn record(&mut self, event: PlayEvent) {
+        *self.events.entry(event).or_default() += 1;
+    }
+
+    pub fn record_death(&mut self, location: String) {
+        self.record(PlayEvent::Death);
+        *self.deaths.entry(location).or_default() += 1;
+    }
+
+    fn count_play_time(time: Res<Time>, mut save: ResMut<SaveData>, mut since_save: Local<f32>) {
+        save.bypass_change_detection().play_time += time.delta_secs();
+
+        *since_save += time.delta_secs();
+        if *since_save >= AUTOSAVE_INTERVAL {
+            *since_save = 0.0;
+            save.set_changed();
+        }
+    }
+
+    fn count_distance(
+        player: Single<&Transform, With<Player>>,
+        mut save: ResMut<SaveData>,
+        mut last: Local<Option<Vec3>>,
+    ) {
+        let position = player.translation;
+        if let Some(last) = *last {
+            let step = position.distance(last);
+            if step < MAX_STEP {
+                save.bypass_change_detection().distance += step;
+            }
+        }
+
+        *last = Some(position);
+    }
+
+    fn restore_dialogue_flags(mut flags: ResMut<DialogueFlags>, save: Res<SaveData>) {
+        flags.extend(save.dialogue_flags.iter().cloned());
+    }
+
+    fn restore_language(mut locale: ResMut<Locale>, save: Res<SaveData>) {
+        locale.language = save.language;
+    }
+
+    fn restore_assists(mut assists: ResMut<Assists>, save: Res<SaveData>) {
+        *assists = save.assists.clone();
+    }
+
+    fn restore_checkpoints(q: Query<&mut Checkpoint, Added<Checkpoint>>, save: Res<SaveData>) {
+        for mut checkpoint in q {
+            if save.checkpoints.contains(&checkpoint.id) {
+                checkpoint.checked = true;
+            }
+        }
+    }
+
+    fn restore_player(
+        player: Single<(&mut Player, &mut PlayerItems), Added<Player>>,
+        checkpoints: Query<(Entity, &Checkpoint)>,
+        save: Res<SaveData>,
+    ) {
+        let (mut player, mut items) = player.into_inner();
+
+        items.extend(save.items.iter().copied());
+        player.dream_tokens = save
+            .found
+            .iter()
+            .filter(|id| id.starts_with(CollectibleKind::Token.prefix()))
+            .count() as u8;
+        player.last_checkpoint = save.last_checkpoint.as_ref().and_then(|id| {
+            checkpoints
+                .iter()
+                .find(|(_, c)| c.id == *id)
+                .map(|(entity, _)| entity)
+        });
+    }
+
+    fn restore_camera(
+        mut camera: Single<&mut PlayerCamera, Added<PlayerCamera>>,
+        save: Res<SaveData>,
+    ) {
+        camera.stick_zoom = save.stick_zoom;
+        camera.auto_follow = save.auto_follow;
+    }
+
+    fn record_items(items: Single<&PlayerItems, Changed<PlayerItems>>, mut save: ResMut<SaveData>) {
+        let mut items = items.iter().copied().collect::<Vec<_>>();
+        items.sort();
+
+        if save.items != items {
+            save.items = items;
+        }
+    }
+
+    fn record_checkpoints(
+        checkpoints: Query<&Checkpoint, Changed<Checkpoint>>,
+        mut save: ResMut<SaveData>,
+    ) {
+        for checkpoint in checkpoints {
+            if checkpoint.checked && !save.checkpoints.contains(&checkpoint.id) {
+                save.checkpoints.insert(checkpoint.id.clone());
+            }
+        }
+    }
+
+    fn record_last_checkpoint(
+        player: Single<&Player, Changed<Player>>,
+        checkpoints: Query<&Checkpoint>,
+        mut save: ResMut<SaveData>,
+    ) {
+        let last = player
+            .last_checkpoint
+            .and_then(|e| checkpoints.get(e).ok())
+            .map(|c| c.id.clone());
+        if last.is_some() && save.last_checkpoint != last {
+            save.last_checkpoint = last;
+        }
+    }
+
+    fn record_dialogue_flags(flags: Res<DialogueFlags>, mut save: ResMut<SaveData>) {
+        if !flags.is_changed() {
+            return;
+        }
+
+        let flags = flags.iter().cloned().collect::<BTreeSet<_>>();
+        if save.dialogue_flags != flags {
+            save.dialogue_flags = flags;
+        }
+    }
+
+    fn record_language(locale: Res<Locale>, mut save: ResMut<SaveData>) {
+        if save.language != locale.language {
+            save.language = locale.language;
+        }
+    }
+
+    fn record_assists(assists: Res<Assists>, mut save: ResMut<SaveData>) {
+        if assists.is_changed() && save.assists != *assists {
+            save.assists = assists.clone();
+        }
+    }
+
+    fn record_camera(
+        camera: Single<&PlayerCamera, Changed<PlayerCamera>>,
+        mut save: ResMut<SaveData>,
+    ) {
+        if save.stick_zoom != camera.stick_zoom {
+            save.stick_zoom = camera.stick_zoom;
+        }
+        if save.auto_follow != camera.auto_follow {
+            save.auto_follow = camera.auto_follow;
+        }
+    }
+}
+
+/// Writes the save in the background
+#[derive(Resource, Default)]
+struct SaveWriter {
+    /// Seconds since the first change that hasn't been written yet
+    pending: Option<f32>,
+    task: Option<Task<Result>>,
+}
+
+impl SaveWriter {
+    fn schedule(save: Res<SaveData>, mut writer: ResMut<SaveWriter>, time: Res<Time<Real>>) {
+        if save.is_changed() && !save.is_added() && writer.pending.is_none() {
+            writer.pending = Some(0.0);
+        }
+
+        let Some(pending) = &mut writer.pending else {
+            return;
+        };
+        *pending += time.delta_secs();
+
+        // Only one write at a time, the next one starts when it's done
+        if *pending < WRITE_DELAY || writer.task.is_some() {
+            return;
+        }
+
+        writer.pending = None;
+        let save = save.clone();
+        writer.task = Some(IoTaskPool::get().spawn(async move { save.write_file() }));
+    }
+
+    fn finish(
+        mut writer: ResMut<SaveWriter>,
+        area: Single<Entity, With<ToastArea>>,
+        mut cmd: Commands,
+    ) {
+        if !writer.task.as_ref().is_some_and(Task::is_finished) {
+            return;
+        }
+
+        let Some(task) = writer.task.take() else {
+            return;
+        };
+
+        if let Err(e) = block_on(task) {
+            warn!("Failed to save: {e}");
+            cmd.spawn((
+                Toast::bundle(children![LocalizedText::new("save.failed")]),
+                ChildOf(*area),
+            ));
+        }
+    }
+
+    /// Keeps the counters that changed since the last autosave
+    fn write_on_exit(
+        save: Res<SaveData>,
+        mut writer: ResMut<SaveWriter>,
+        mut exit: MessageReader<AppExit>,
+    ) {
+        if exit.read().count() == 0 {
+            return;
+        }
+
+        // Let a write in progress finish, this one is newer
+        if let Some(task) = writer.task.take() {
+            block_on(task).ok();
+        }
+
+        if let Err(e) = save.write_file() {
+            warn!("Failed to save: {e}");
+        }
+    }
+}
+
+impl SaveData {
+    fn write_file(&self) -> Result {
+        // Writes from the task pool and on exit could overlap on the temporary file
+        static WRITING: Mutex<()> = Mutex::new(());
+        let _lock = WRITING.lock().unwrap_or_else(|e| e.into_inner());
+
+        let text = ron::ser::to_string_pretty(self, default())?;
+        write_atomic(&text)?;
+        Ok(())
+    }
+}
+
+fn write_atomic(text: &str) -> io::Result<()> {
+    let mut file = File::create(TEMP_PATH)?;
+    file.write_all(text.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(TEMP_PATH, SAVE_PATH)
+}
@@ -0,0 +1,197 @@
+use std::{
+    fs::{self, File},
+    io::{LineWriter, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{
+    prelude::*,
+    render::view::screenshot::{Screenshot, save_to_disk},
+};
+use serde::Serialize;
+
+use crate::{
+    GameState,
+    player::{Player, item::Item},
+    ui::screen::map::{MapCaptured, MapImage},
+};
+
+const TELEMETRY_DIR: &str = "telemetry";
+/// Telemetry is only logged when this environment variable is set
+const TELEMETRY_VAR: &str = "DREAMSEEKER_TELEMETRY";
+/// How often the player's position is logged, in seconds
+const SAMPLE_INTERVAL: f32 = 0.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_message::<Telemetry>()
+        .init_resource::<TelemetryLog>()
+        .add_systems(
+            Update,
+            Telemetry::sample_position
+                .run_if(in_state(GameState::InGame).and(TelemetryLog::enabled)),
+        )
+        .add_systems(PostUpdate, TelemetryLog::write)
+        .add_observer(Telemetry::on_map_captured);
+}
+
+/// Something worth looking at after a playtest. Written to the session's log as a line of JSON
+#[derive(Message, Serialize, Clone, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Telemetry {
+    /// The first line of every log
+    Session {
+        unix_time: u64,
+    },
+    /// The area the world map covers, as `[min_x, min_z, max_x, max_z]`. The capture of
+    /// the map is saved next to the log, at `telemetry/<unix time>.map.png`
+    Level {
+        bounds: [f32; 4],
+    },
+    Position {
+        position: [f32; 3],
+    },
+    /// `cause` is the name or position of what killed the player
+    Death {
+        cause: String,
+        position: [f32; 3],
+    },
+    Checkpoint {
+        id: String,
+        position: [f32; 3],
+    },
+    Item {
+        item: Item,
+        position: [f32; 3],
+    },
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    /// Seconds since the game started
+    time: f64,
+    #[serde(flatten)]
+    event: &'a Telemetry,
+}
+
+/// The log of this session, at `telemetry/<unix time>.jsonl`. Off unless `DREAMSEEKER_TELEMETRY`
+/// is set
+#[derive(Resource)]
+struct TelemetryLog {
+    file: Option<LineWriter<File>>,
+    unix_time: u64,
+}
+
+impl FromWorld for TelemetryLog {
+    fn from_world(world: &mut World) -> Self {
+        if std::env::var_os(TELEMETRY_VAR).is_none() {
+            return Self {
+                file: None,
+                unix_time: 0,
+            };
+        }
+
+        let unix_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        let file = fs::create_dir_all(TELEMETRY_DIR)
+            .and_then(|_| File::create(format!("{TELEMETRY_DIR}/{unix_time}.jsonl")));
+
+        match file {
+            Ok(file) => {
+                world.write_message(Telemetry::Session { unix_time });
+                Self {
+                    file: Some(LineWriter::new(file)),
+                    unix_time,
+                }
+            }
+            Err(e) => {
+                warn!("Telemetry is off, the log couldn't be created: {e}");
+                Self {
+                    file: None,
+                    unix_time,
+                }
+            }
+        }
+    }
+}
+
+impl TelemetryLog {
+    fn enabled(log: Res<TelemetryLog>) -> bool {
+        log.file.is_some()
+    }
+
+    fn write(
+        mut log: ResMut<TelemetryLog>,
+        mut msg: MessageReader<Telemetry>,
+        time: Res<Time<Real>>,
+    ) {
+        let Some(file) = &mut log.file else {
+            msg.clear();
+            return;
+        };
+
+        for event in msg.read() {
+            let record = Record {
+                time: time.elapsed_secs_f64(),
+                event,
+            };
+
+            let result = serde_json::to_writer(&mut *file, &record)
+                .map_err(BevyError::from)
+                .and_then(|_| Ok(writeln!(file)?));
+
+            if let Err(e) = result {
+                warn!("Telemetry is off, the log couldn't be written: {e}");
+                log.file = None;
+                return;
+            }
+        }
+    }
+}
+
+impl Telemetry {
+    fn sample_position(
+        player: Single<&Transform, With<Player>>,
+        mut msg: MessageWriter<Telemetry>,
+        mut since_sample: Local<f32>,
+        time: Res<Time>,
+    ) {
+        *since_sample += time.delta_secs();
+        if *since_sample >= SAMPLE_INTERVAL {
+            *since_sample = 0.0;
+            msg.write(Telemetry::Position {
+                position: player.translation.to_array(),
+            });
+        }
+    }
+
+    /// Logs the bounds of the level and saves the map, for the heatmap to draw under the heat
+    fn on_map_captured(
+        event: On<MapCaptured>,
+        maps: Query<(&GlobalTransform, &MapImage)>,
+        log: Res<TelemetryLog>,
+        mut msg: MessageWriter<Telemetry>,
+        mut cmd: Commands,
+    ) {
+        if log.file.is_none() {
+            return;
+        }
+        let Ok((transform, image)) = maps.get(event.0) else {
+            return;
+        };
+
+        let center = transform.translation().xz();
+        let half = transform.scale().xz().abs() / 2.0;
+        let (min, max) = (center - half, center + half);
+        msg.write(Telemetry::Level {
+            bounds: [min.x, min.y, max.x, max.y],
+        });
+
+        cmd.spawn(Screenshot::image(image.0.clone()))
+            .observe(save_to_disk(format!(
+                "{TELEMETRY_DIR}/{}.map.png",
+                log.unix_time
+            )));
+    }
+}
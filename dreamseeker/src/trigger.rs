@@ -1,13 +1,28 @@
+use std::time::Duration;
+
 use avian3d::prelude::*;
 use bevy::{
     ecs::{lifecycle::HookContext, world::DeferredWorld},
     prelude::*,
 };
+use bevy_flurx::{
+    action::{delay, once, wait},
+    prelude::Reactor,
+    task::ReactorTask,
+};
 
 use crate::{
-    GameState,
+    GameState, Sounds,
+    assist::Assists,
     collision::GameLayer,
-    player::{Die, Player},
+    locale::{LocalizedText, Strings},
+    player::{
+        Die, Player,
+        cinematic::{CameraPath, Cinematic, CinematicPath},
+        shockwave::{SlamHit, SlamTarget},
+    },
+    save::SaveData,
+    telemetry::Telemetry,
     ui::screen::{ScreenCommandsExt, ScreenStack, end::EndScreen, info::InfoScreen},
 };
 
@@ -83,11 +98,46 @@ impl DeathTrigger {
             .observe(Self::on_collision);
     }
 
-    fn on_collision(event: On<CollisionStart>, mut cmd: Commands) {
+    fn on_collision(
+        event: On<CollisionStart>,
+        player: Query<&Transform, With<Player>>,
+        triggers: Query<(Option<&Name>, &GlobalTransform)>,
+        assists: Res<Assists>,
+        mut save: ResMut<SaveData>,
+        mut telemetry: MessageWriter<Telemetry>,
+        mut cmd: Commands,
+    ) {
+        // Invincible players are still taken out of pits, but it doesn't count as a death
+        if !assists.invincible
+            && let Ok(player) = player.get(event.collider2)
+            && let Ok((name, transform)) = triggers.get(event.collider1)
+        {
+            let location = match name {
+                Some(name) => name.to_string(),
+                None => {
+                    let Vec3 { x, y, z } = transform.translation().round();
+                    format!("{x}, {y}, {z}")
+                }
+            };
+            telemetry.write(Telemetry::Death {
+                cause: location.clone(),
+                position: player.translation.to_array(),
+            });
+            save.record_death(location);
+        }
         cmd.trigger(Die(event.collider2));
     }
 }
 
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component, Default)]
+#[require(
+    Transform,
+    Sensor,
+    CollisionLayers::new(GameLayer::Water, LayerMask::ALL)
+)]
+pub struct Water;
+
 #[derive(Component, Reflect, Default)]
 #[reflect(Component, Default)]
 #[require(Transform)]
@@ -104,6 +154,7 @@ impl InitialSpawn {
     }
 }
 
+/// A sign showing a popup while the player is near. The text is a string table key
 #[derive(Component, Reflect, Clone, Default)]
 #[reflect(Component, Default)]
 #[require(
@@ -139,11 +190,15 @@ impl InfoTrigger {
         event: On<CollisionStart>,
         info: Query<&InfoTrigger>,
         player: Query<&Player>,
+        strings: Res<Strings>,
         mut cmd: Commands,
     ) -> Result {
         if player.contains(event.collider2) {
             let info = info.get(event.collider1)?;
-            cmd.push_screen(InfoScreen::bundle(info.0.clone()));
+            if !strings.contains(&info.0) {
+                warn!("Sign text isn't in the string tables: {:?}", info.0);
+            }
+            cmd.push_screen(InfoScreen::bundle(LocalizedText::new(info.0.clone())));
         }
         Ok(())
     }
@@ -155,11 +210,11 @@ impl InfoTrigger {
         screen: Res<ScreenStack>,
         mut cmd: Commands,
     ) {
+        // A menu may have opened on top of the popup since it was pushed
         if player.contains(event.collider2)
-            && let Some(cur) = screen.current()
-            && q.contains(cur)
+            && let Some(info) = screen.iter().find(|&e| q.contains(e))
         {
-            cmd.pop_screen();
+            cmd.close_screen(info);
         }
     }
 }
@@ -201,16 +256,20 @@ impl Checkpoint {
     fn on_enter(
         event: On<CollisionStart>,
         mut q: Query<&mut Checkpoint>,
-        mut player: Query<&mut Player>,
+        mut player: Query<(&mut Player, &Transform)>,
+        mut telemetry: MessageWriter<Telemetry>,
         mut cmd: Commands,
     ) -> Result {
-        if let Ok(mut player) = player.get_mut(event.collider2) {
+        if let Ok((mut player, transform)) = player.get_mut(event.collider2) {
             let mut checkpoint = q.get_mut(event.collider1)?;
             player.last_checkpoint = Some(event.collider1);
-            cmd.push_screen(InfoScreen::bundle(format!(
-                "Checkpoint {} unlocked. Press T / Select to open the teleport menu",
-                checkpoint.id
-            )));
+            telemetry.write(Telemetry::Checkpoint {
+                id: checkpoint.id.clone(),
+                position: transform.translation.to_array(),
+            });
+            cmd.push_screen(InfoScreen::bundle(
+                LocalizedText::new("checkpoint.unlocked").with("id", &checkpoint.id),
+            ));
             checkpoint.checked = true;
         }
         Ok(())
@@ -223,11 +282,11 @@ impl Checkpoint {
         screen: Res<ScreenStack>,
         mut cmd: Commands,
     ) {
+        // A menu may have opened on top of the popup since it was pushed
         if player.contains(event.collider2)
-            && let Some(cur) = screen.current()
-            && q.contains(cur)
+            && let Some(info) = screen.iter().find(|&e| q.contains(e))
         {
-            cmd.pop_screen();
+            cmd.close_screen(info);
         }
     }
 
@@ -283,6 +342,147 @@ pub struct Bouncy;
 #[reflect(Component, Default)]
 pub struct CameraNoClip;
 
+/// Destroyed by the shockwave of a slam
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+#[require(Transform, SlamTarget)]
+#[component(on_add)]
+pub struct Breakable;
+
+impl Breakable {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        world.commands().entity(ctx.entity).observe(Self::on_slam);
+    }
+
+    fn on_slam(event: On<SlamHit>, sounds: Res<Sounds>, mut cmd: Commands) {
+        cmd.spawn((
+            AudioPlayer::new(sounds.sword_hit.clone()),
+            PlaybackSettings::DESPAWN,
+        ));
+        cmd.entity(event.entity).despawn();
+    }
+}
+
+/// Pressed by the shockwave of a slam, removing every [`SwitchGate`] with the same id
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component, Default)]
+#[require(Transform, SlamTarget)]
+#[component(on_add)]
+pub struct SlamSwitch {
+    pub id: String,
+    pub pressed: bool,
+}
+
+impl SlamSwitch {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        world.commands().entity(ctx.entity).observe(Self::on_slam);
+    }
+
+    fn on_slam(
+        event: On<SlamHit>,
+        mut q: Query<(&mut SlamSwitch, &mut Transform)>,
+        sounds: Res<Sounds>,
+        mut cmd: Commands,
+    ) -> Result {
+        let (mut switch, mut transform) = q.get_mut(event.entity)?;
+        if switch.pressed {
+            return Ok(());
+        }
+
+        switch.pressed = true;
+        transform.translation.y -= 0.2;
+
+        cmd.spawn((
+            AudioPlayer::new(sounds.chest_open.clone()),
+            PlaybackSettings::DESPAWN,
+        ));
+
+        let id = switch.id.clone();
+        cmd.spawn(Reactor::schedule(move |task| open_gates(task, id)));
+
+        Ok(())
+    }
+}
+
+/// Shows the gates opening, using the [`CinematicPath`] with the switch's id if there is one
+async fn open_gates(task: ReactorTask, id: String) {
+    let authored = task
+        .will(Update, once::run(CinematicPath::find(id.clone())))
+        .await;
+
+    let gate_id = id.clone();
+    let path = task
+        .will(
+            Update,
+            once::run(
+                move |gates: Query<(&SwitchGate, &GlobalTransform)>,
+                      player: Single<&Transform, With<Player>>| {
+                    let (_, gate) = gates.iter().find(|(gate, _)| gate.0 == gate_id)?;
+                    Some(CameraPath::orbit(
+                        gate.translation() + Vec3::Y * 2.0,
+                        player.translation,
+                        8.0,
+                        3.0,
+                        2.5,
+                    ))
+                },
+            ),
+        )
+        .await;
+
+    let Some(path) = authored.or(path) else {
+        return;
+    };
+    let duration = path.duration();
+
+    task.will(
+        Update,
+        once::run(move |mut cinematic: ResMut<Cinematic>, mut cmd: Commands| {
+            cmd.set_state(GameState::Cutscene);
+            cinematic.start(CameraPath {
+                hold: true,
+                ..path.clone()
+            });
+        }),
+    )
+    .await;
+
+    task.will(
+        Update,
+        delay::time().with(Duration::from_secs_f32(duration * 0.5)),
+    )
+    .await;
+
+    task.will(
+        Update,
+        once::run(
+            move |gates: Query<(Entity, &SwitchGate)>, mut cmd: Commands| {
+                for (entity, gate) in gates {
+                    if gate.0 == id {
+                        cmd.entity(entity).despawn();
+                    }
+                }
+            },
+        ),
+    )
+    .await;
+
+    task.will(Update, wait::until(Cinematic::finished)).await;
+
+    task.will(
+        Update,
+        once::run(|mut cinematic: ResMut<Cinematic>, mut cmd: Commands| {
+            cinematic.stop();
+            cmd.set_state(GameState::InGame);
+        }),
+    )
+    .await;
+}
+
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component, Default)]
+pub struct SwitchGate(pub String);
+
 #[derive(Component, Reflect, Default)]
 #[reflect(Component, Default)]
 #[require(
@@ -310,12 +510,72 @@ impl Door {
             .insert(SceneRoot(scene));
     }
 
-    fn on_enter(event: On<CollisionStart>, player: Query<&Player>, mut cmd: Commands) -> Result {
+    fn on_enter(
+        event: On<CollisionStart>,
+        player: Query<&Player>,
+        state: Res<State<GameState>>,
+        mut cmd: Commands,
+    ) -> Result {
+        if *state.get() != GameState::InGame {
+            return Ok(());
+        }
+
         if let Ok(player) = player.get(event.collider2) {
             if player.dream_tokens >= 10 {
-                cmd.push_screen(EndScreen::bundle());
+                let door = event.collider1;
+                cmd.spawn(Reactor::schedule(move |task| open_door(task, door)));
             }
         }
         Ok(())
     }
 }
+
+/// Circles the door, or plays the `door` [`CinematicPath`], before the end screen
+async fn open_door(task: ReactorTask, door: Entity) {
+    let authored = task
+        .will(Update, once::run(CinematicPath::find("door")))
+        .await;
+
+    let path = task
+        .will(
+            Update,
+            once::run(
+                move |doors: Query<&GlobalTransform>, player: Single<&Transform, With<Player>>| {
+                    let door = doors.get(door).ok()?;
+                    Some(CameraPath::orbit(
+                        door.translation() + Vec3::Y * 2.0,
+                        player.translation,
+                        7.0,
+                        2.5,
+                        3.0,
+                    ))
+                },
+            ),
+        )
+        .await;
+
+    if let Some(path) = authored.or(path) {
+        task.will(
+            Update,
+            once::run(move |mut cinematic: ResMut<Cinematic>, mut cmd: Commands| {
+                cmd.set_state(GameState::Cutscene);
+                cinematic.start(CameraPath {
+                    hold: true,
+                    ..path.clone()
+                });
+            }),
+        )
+        .await;
+
+        task.will(Update, wait::until(Cinematic::finished)).await;
+    }
+
+    task.will(
+        Update,
+        once::run(|mut cinematic: ResMut<Cinematic>, mut cmd: Commands| {
+            cinematic.stop();
+            cmd.push_screen(EndScreen::bundle());
+        }),
+    )
+    .await;
+}
@@ -1,10 +1,15 @@
 use bevy::prelude::*;
 
 pub mod screen;
+pub mod toast;
 pub mod trans;
 
 pub use self::screen::Screen;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((self::screen::plugin, self::trans::plugin));
+    app.add_plugins((
+        self::screen::plugin,
+        self::toast::plugin,
+        self::trans::plugin,
+    ));
 }
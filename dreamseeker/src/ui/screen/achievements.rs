@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use dreamseeker_util::{construct::Make, observers};
+
+use crate::{
+    GameState,
+    achievement::{Achievement, AchievementList, Achievements},
+    input::ui::actions,
+    locale::LocalizedText,
+    save::SaveData,
+};
+
+use super::{Screen, ScreenAnimation, ScreenShown};
+
+const UNLOCKED_COLOR: Color = Color::srgb(1.0, 0.8, 0.4);
+const LOCKED_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+
+pub(super) fn plugin(_app: &mut App) {}
+
+#[derive(Component)]
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
+pub struct AchievementsScreen;
+
+impl AchievementsScreen {
+    pub fn bundle() -> impl Bundle {
+        let title = (
+            LocalizedText::new("achievements.title"),
+            TextFont::from_font_size(36.0),
+        );
+
+        let help = (
+            LocalizedText::new("settings.help"),
+            TextFont::from_font_size(20.0),
+        );
+
+        (
+            Self,
+            Node {
+                width: percent(100),
+                height: percent(100),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: px(20),
+                ..default()
+            },
+            BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.8)),
+            actions(),
+            observers![Self::on_shown],
+            children![title, Make(Self::make_list), help],
+        )
+    }
+
+    fn make_list(
+        achievements: Res<Achievements>,
+        lists: Res<Assets<AchievementList>>,
+        save: Res<SaveData>,
+    ) -> Result<impl Bundle + use<>> {
+        let list = achievements.list(&lists);
+        let unlocked = list
+            .iter()
+            .filter(|a| save.achievements.contains(&a.id))
+            .count();
+
+        let count = (
+            LocalizedText::new("achievements.count")
+                .with("unlocked", unlocked)
+                .with("total", list.len()),
+            TextFont::from_font_size(24.0),
+        );
+
+        let entries = list
+            .iter()
+            .map(|a| AchievementEntry::bundle(a, save.achievements.contains(&a.id)))
+            .collect::<Vec<_>>();
+
+        Ok((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: px(10),
+                ..default()
+            },
+            Children::spawn((Spawn(count), SpawnIter(entries.into_iter()))),
+        ))
+    }
+
+    fn on_shown(_: On<ScreenShown>, mut state: ResMut<NextState<GameState>>) {
+        state.set(GameState::Paused);
+    }
+}
+
+#[derive(Component)]
+struct AchievementEntry;
+
+impl AchievementEntry {
+    fn bundle(achievement: &Achievement, unlocked: bool) -> impl Bundle + use<> {
+        let (name, description) = if achievement.hidden && !unlocked {
+            (
+                LocalizedText::new("achievements.hidden"),
+                LocalizedText::new("achievements.hidden_description"),
+            )
+        } else {
+            (achievement.name(), achievement.description())
+        };
+        let color = if unlocked {
+            UNLOCKED_COLOR
+        } else {
+            LOCKED_COLOR
+        };
+
+        (
+            Self,
+            Node {
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(px(5)),
+                ..default()
+            },
+            BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.3)),
+            Outline::new(px(1), px(0), color),
+            children![
+                (name, TextFont::from_font_size(24.0), TextColor(color)),
+                (description, TextFont::from_font_size(18.0)),
+            ],
+        )
+    }
+}
@@ -0,0 +1,187 @@
+use bevy::prelude::*;
+use dreamseeker_util::{construct::Make, observers};
+
+use crate::{GameState, assist::Assists, input::ui::actions, locale::LocalizedText};
+
+use super::{
+    Screen, ScreenAnimation, ScreenShown,
+    widget::{Slider, Toggle, WidgetChanged, list, slider, toggle},
+};
+
+const GAME_SPEED_STEP: f32 = 0.1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, AssistScreen::update);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AssistOption {
+    GameSpeed,
+    InfiniteAirJumps,
+    InfiniteDash,
+    Invincible,
+    LongCoyoteTime,
+}
+
+impl AssistOption {
+    const ALL: [Self; 5] = [
+        Self::GameSpeed,
+        Self::InfiniteAirJumps,
+        Self::InfiniteDash,
+        Self::Invincible,
+        Self::LongCoyoteTime,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::GameSpeed => "assist.game_speed",
+            Self::InfiniteAirJumps => "assist.infinite_air_jumps",
+            Self::InfiniteDash => "assist.infinite_dash",
+            Self::Invincible => "assist.invincible",
+            Self::LongCoyoteTime => "assist.long_coyote_time",
+        }
+    }
+
+    fn enabled(self, assists: &Assists) -> Option<bool> {
+        match self {
+            Self::GameSpeed => None,
+            Self::InfiniteAirJumps => Some(assists.infinite_air_jumps),
+            Self::InfiniteDash => Some(assists.infinite_dash),
+            Self::Invincible => Some(assists.invincible),
+            Self::LongCoyoteTime => Some(assists.long_coyote_time),
+        }
+    }
+
+    fn toggle(self, assists: &mut Assists) -> Option<&mut bool> {
+        match self {
+            Self::GameSpeed => None,
+            Self::InfiniteAirJumps => Some(&mut assists.infinite_air_jumps),
+            Self::InfiniteDash => Some(&mut assists.infinite_dash),
+            Self::Invincible => Some(&mut assists.invincible),
+            Self::LongCoyoteTime => Some(&mut assists.long_coyote_time),
+        }
+    }
+}
+
+#[derive(Component)]
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
+pub struct AssistScreen;
+
+/// Which assist a widget changes
+#[derive(Component, Clone, Copy)]
+struct AssistWidget(AssistOption);
+
+#[derive(Component)]
+struct GameSpeedLabel;
+
+impl AssistScreen {
+    pub fn bundle() -> impl Bundle {
+        let title = (
+            LocalizedText::new("assist.title"),
+            TextFont::from_font_size(36.0),
+        );
+
+        let help = (
+            LocalizedText::new("assist.help"),
+            TextFont::from_font_size(20.0),
+        );
+
+        (
+            AssistScreen,
+            Node {
+                width: percent(100),
+                height: percent(100),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: px(20),
+                ..default()
+            },
+            BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.8)),
+            actions(),
+            observers![Self::on_shown],
+            children![title, Make(Self::make_list), help],
+        )
+    }
+
+    fn make_list(assists: Res<Assists>) -> Result<impl Bundle + use<>> {
+        let speed = (
+            slider(
+                (
+                    GameSpeedLabel,
+                    LocalizedText::new(AssistOption::GameSpeed.key())
+                        .with("value", speed_percent(&assists)),
+                    TextFont::from_font_size(24.0),
+                ),
+                Slider {
+                    value: assists.game_speed,
+                    min: Assists::MIN_GAME_SPEED,
+                    max: 1.0,
+                    step: GAME_SPEED_STEP,
+                },
+            ),
+            AssistWidget(AssistOption::GameSpeed),
+            observers![Self::on_changed],
+        );
+
+        let toggles = AssistOption::ALL
+            .into_iter()
+            .filter_map(|option| {
+                let on = option.enabled(&assists)?;
+                let label = (
+                    LocalizedText::new(option.key()),
+                    TextFont::from_font_size(24.0),
+                );
+                Some((
+                    toggle(label, on),
+                    AssistWidget(option),
+                    observers![Self::on_changed],
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        Ok((
+            list(),
+            Children::spawn((Spawn(speed), SpawnIter(toggles.into_iter()))),
+        ))
+    }
+
+    fn update(assists: Res<Assists>, mut labels: Query<&mut LocalizedText, With<GameSpeedLabel>>) {
+        if !assists.is_changed() {
+            return;
+        }
+
+        for mut label in &mut labels {
+            label.set("value", speed_percent(&assists));
+        }
+    }
+
+    fn on_changed(
+        event: On<WidgetChanged>,
+        widgets: Query<(&AssistWidget, Option<&Toggle>, Option<&Slider>)>,
+        mut assists: ResMut<Assists>,
+    ) -> Result {
+        let (AssistWidget(option), toggle, slider) = widgets.get(event.0)?;
+
+        if let Some(slider) = slider {
+            assists.game_speed = slider.value;
+        }
+        if let Some(toggle) = toggle
+            && let Some(value) = option.toggle(&mut assists)
+        {
+            *value = toggle.0;
+        }
+
+        Ok(())
+    }
+
+    fn on_shown(_: On<ScreenShown>, mut state: ResMut<NextState<GameState>>) {
+        state.set(GameState::Paused);
+    }
+}
+
+fn speed_percent(assists: &Assists) -> String {
+    format!("{:.0}%", assists.game_speed * 100.0)
+}
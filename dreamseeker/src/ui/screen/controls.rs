@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use dreamseeker_util::observers;
+
+use crate::{GameState, input::ui::actions, locale::LocalizedText};
+
+use super::{Screen, ScreenAnimation, ScreenShown, widget::list};
+
+/// Actions listed on the screen, each with a `controls.<name>` string that shows its prompt
+const CONTROLS: [&str; 13] = [
+    "move",
+    "walk",
+    "jump",
+    "slide",
+    "dash",
+    "wall_grab",
+    "attack",
+    "grapple",
+    "move_camera",
+    "zoom",
+    "center_camera",
+    "teleport",
+    "pause",
+];
+
+pub(super) fn plugin(_app: &mut App) {}
+
+/// The bindings of every action, for the device that was used last
+#[derive(Component)]
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
+pub struct ControlsScreen;
+
+impl ControlsScreen {
+    pub fn bundle() -> impl Bundle {
+        let title = (
+            LocalizedText::new("controls.title"),
+            TextFont::from_font_size(36.0),
+        );
+
+        let controls = (
+            list(),
+            Children::spawn(SpawnIter(CONTROLS.into_iter().map(|name| {
+                (
+                    LocalizedText::new(format!("controls.{name}")),
+                    TextFont::from_font_size(22.0),
+                )
+            }))),
+        );
+
+        let help = (
+            LocalizedText::new("settings.help"),
+            TextFont::from_font_size(20.0),
+        );
+
+        (
+            Self,
+            Node {
+                width: percent(100),
+                height: percent(100),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: px(20),
+                ..default()
+            },
+            BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.8)),
+            actions(),
+            observers![Self::on_shown],
+            children![title, controls, help],
+        )
+    }
+
+    fn on_shown(_: On<ScreenShown>, mut state: ResMut<NextState<GameState>>) {
+        state.set(GameState::Paused);
+    }
+}
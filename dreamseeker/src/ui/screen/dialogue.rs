@@ -0,0 +1,419 @@
+use bevy::{
+    asset::LoadState,
+    ecs::system::SystemParam,
+    prelude::*,
+    window::{CursorGrabMode, CursorOptions, PrimaryWindow},
+};
+use bevy_enhanced_input::prelude::Start;
+use dreamseeker_util::observers;
+
+use crate::{
+    GameState,
+    dialogue::{Dialogue, DialogueFlags, DialogueNode},
+    input::ui::{Confirm, actions},
+    locale::{NoLocalize, Strings},
+    prompt::Prompts,
+};
+
+use super::{
+    Screen, ScreenAnimation, ScreenCommandsExt, ScreenHidden, ScreenShown, ScreenStack,
+    pointer::ScreenClicked,
+    widget::{self, Activate, Focus},
+};
+
+const CHARS_PER_SECOND: f32 = 40.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            DialogueScreen::start,
+            DialogueScreen::reveal,
+            DialogueScreen::update,
+            DialogueScreen::update_choices,
+        )
+            .chain(),
+    );
+}
+
+#[derive(Component)]
+#[require(Screen {
+    overlay: true,
+    animation: ScreenAnimation::SlideUp,
+    ..default()
+})]
+pub struct DialogueScreen {
+    dialogue: Handle<Dialogue>,
+    node: Option<String>,
+    page: usize,
+    revealed: f32,
+    /// Counts the nodes entered, so the choice buttons know when they are out of date
+    entered: u32,
+    /// The visit to a node that the choice buttons were spawned for
+    choices: Option<u32>,
+}
+
+#[derive(Component)]
+struct Speaker;
+
+#[derive(Component)]
+struct Body;
+
+/// Holds a button for each choice of the current node
+#[derive(Component)]
+struct ChoiceList;
+
+/// A button for one of the choices of the current node
+#[derive(Component)]
+struct Choice(usize);
+
+/// The dialogue assets, and what is needed to show their text
+#[derive(SystemParam)]
+struct DialogueText<'w> {
+    dialogues: Res<'w, Assets<Dialogue>>,
+    strings: Res<'w, Strings>,
+    prompts: Res<'w, Prompts>,
+}
+
+impl DialogueText<'_> {
+    fn node(&self, screen: &DialogueScreen) -> Option<&DialogueNode> {
+        let id = screen.node.as_ref()?;
+        self.dialogues.get(&screen.dialogue)?.nodes.get(id)
+    }
+
+    /// The current page, which can be a string table key
+    fn page(&self, screen: &DialogueScreen, node: &DialogueNode) -> String {
+        node.pages
+            .get(screen.page)
+            .map(|page| self.prompts.fill(self.strings.get(page)))
+            .unwrap_or_default()
+    }
+}
+
+impl DialogueScreen {
+    pub fn bundle(dialogue: Handle<Dialogue>) -> impl Bundle {
+        let speaker = (
+            Speaker,
+            Text::default(),
+            NoLocalize,
+            TextFont::from_font_size(24.0),
+            TextColor(Color::linear_rgb(1.0, 0.8, 0.4)),
+        );
+
+        let body = (
+            Body,
+            Text::default(),
+            NoLocalize,
+            TextFont::from_font_size(20.0),
+            TextLayout::new(Justify::Left, LineBreak::WordOrCharacter),
+        );
+
+        let choices = (
+            ChoiceList,
+            Node {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+        );
+
+        (
+            Self {
+                dialogue,
+                node: None,
+                page: 0,
+                revealed: 0.0,
+                entered: 0,
+                choices: None,
+            },
+            Node {
+                width: percent(100),
+                height: percent(35),
+                align_self: AlignSelf::End,
+                flex_direction: FlexDirection::Column,
+                row_gap: px(10),
+                padding: UiRect::axes(percent(15), px(20)),
+                border: UiRect::top(px(1)),
+                ..default()
+            },
+            BorderColor::all(Color::WHITE),
+            BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.6)),
+            actions(),
+            observers![
+                Self::on_confirm,
+                Self::on_shown,
+                Self::on_hidden,
+                Self::on_click
+            ],
+            children![speaker, body, choices],
+        )
+    }
+
+    /// Enters `id`, or closes the dialogue screen `entity` when there is nowhere to go
+    fn goto(
+        &mut self,
+        entity: Entity,
+        id: Option<String>,
+        dialogue: &Dialogue,
+        flags: &mut DialogueFlags,
+        cmd: &mut Commands,
+    ) {
+        let Some((id, node)) = id.and_then(|id| dialogue.node(&id, flags)) else {
+            self.node = None;
+            cmd.close_screen(entity);
+            return;
+        };
+
+        flags.extend(node.set_flags.iter().cloned());
+
+        self.node = Some(id);
+        self.page = 0;
+        self.revealed = 0.0;
+        self.entered += 1;
+    }
+
+    /// Whether every page of `node` has been shown in full
+    fn finished(&self, node: &DialogueNode, page: &str) -> bool {
+        self.revealed as usize >= page.chars().count() && self.page + 1 >= node.pages.len()
+    }
+
+    fn start(
+        screens: Query<(Entity, &mut DialogueScreen)>,
+        dialogues: Res<Assets<Dialogue>>,
+        assets: Res<AssetServer>,
+        stack: Res<ScreenStack>,
+        mut flags: ResMut<DialogueFlags>,
+        mut cmd: Commands,
+    ) {
+        for (entity, mut screen) in screens {
+            if screen.node.is_some() || !stack.iter().any(|e| e == entity) {
+                continue;
+            }
+
+            // Close instead of leaving the game stuck in the cutscene state
+            if let LoadState::Failed(e) = assets.load_state(&screen.dialogue) {
+                warn!("Closing the dialogue, it couldn't be loaded: {e}");
+                cmd.close_screen(entity);
+                continue;
+            }
+
+            let Some(dialogue) = dialogues.get(&screen.dialogue) else {
+                continue;
+            };
+
+            let start = dialogue.start.clone();
+            screen.goto(entity, Some(start), dialogue, &mut flags, &mut cmd);
+        }
+    }
+
+    fn reveal(screens: Query<&mut DialogueScreen>, time: Res<Time>) {
+        for mut screen in screens {
+            screen.revealed += CHARS_PER_SECOND * time.delta_secs();
+        }
+    }
+
+    fn update(
+        screens: Query<(Entity, &DialogueScreen)>,
+        children: Query<&Children>,
+        text: DialogueText,
+        mut speaker: Query<&mut Text, (With<Speaker>, Without<Body>)>,
+        mut body: Query<&mut Text, With<Body>>,
+    ) {
+        for (entity, screen) in screens {
+            let Some(node) = text.node(screen) else {
+                continue;
+            };
+
+            let revealed = text
+                .page(screen, node)
+                .chars()
+                .take(screen.revealed as usize)
+                .collect::<String>();
+
+            for child in children.iter_descendants(entity) {
+                if let Ok(mut speaker) = speaker.get_mut(child) {
+                    speaker.0 = node
+                        .speaker
+                        .as_ref()
+                        .map(|speaker| text.strings.get(speaker))
+                        .unwrap_or_default();
+                }
+
+                if let Ok(mut body) = body.get_mut(child) {
+                    body.0 = revealed.clone();
+                }
+            }
+        }
+    }
+
+    /// Spawns a button for each choice once the last page is shown, and clears them
+    /// when the node changes
+    fn update_choices(
+        screens: Query<(Entity, &mut DialogueScreen)>,
+        children: Query<&Children>,
+        lists: Query<Entity, With<ChoiceList>>,
+        text: DialogueText,
+        flags: Res<DialogueFlags>,
+        mut cmd: Commands,
+    ) {
+        for (entity, mut screen) in screens {
+            let Some(node) = text.node(&screen) else {
+                continue;
+            };
+
+            let page = text.page(&screen, node);
+            let wanted = screen.finished(node, &page).then_some(screen.entered);
+            if screen.choices == wanted {
+                continue;
+            }
+            screen.choices = wanted;
+
+            let Some(list) = lists.iter_many(children.iter_descendants(entity)).next() else {
+                continue;
+            };
+
+            let mut list = cmd.entity(list);
+            list.despawn_related::<Children>();
+
+            if wanted.is_none() {
+                continue;
+            }
+
+            for (i, choice) in node.choices(&flags).enumerate() {
+                let label = (
+                    Text::new(text.strings.get(&choice.text)),
+                    NoLocalize,
+                    TextFont::from_font_size(20.0),
+                );
+
+                list.with_child((
+                    Choice(i),
+                    widget::button(label),
+                    observers![Self::on_choice],
+                ));
+            }
+        }
+    }
+
+    /// The focused choice takes the confirm while there are choices to pick
+    fn on_confirm(event: On<Start<Confirm>>, screens: Query<&Focus>, mut cmd: Commands) {
+        if screens
+            .get(event.context)
+            .is_ok_and(|focus| focus.0.is_none())
+        {
+            cmd.run_system_cached_with(Self::confirm, event.context);
+        }
+    }
+
+    fn on_click(event: On<ScreenClicked>, mut cmd: Commands) {
+        cmd.run_system_cached_with(Self::confirm, event.0);
+    }
+
+    fn on_choice(
+        event: On<Activate>,
+        parents: Query<&ChildOf>,
+        choices: Query<&Choice>,
+        screens: Query<(), With<DialogueScreen>>,
+        mut cmd: Commands,
+    ) {
+        let Ok(&Choice(index)) = choices.get(event.0) else {
+            return;
+        };
+        let Some(screen) = parents
+            .iter_ancestors(event.0)
+            .find(|&e| screens.contains(e))
+        else {
+            return;
+        };
+
+        cmd.run_system_cached_with(Self::choose, (screen, index));
+    }
+
+    /// Skips the typewriter, then advances pages, then follows the node when it has no choices
+    fn confirm(
+        In(entity): In<Entity>,
+        mut screen: Query<&mut DialogueScreen>,
+        text: DialogueText,
+        mut flags: ResMut<DialogueFlags>,
+        mut cmd: Commands,
+    ) {
+        let Ok(mut screen) = screen.get_mut(entity) else {
+            return;
+        };
+        let Some(dialogue) = text.dialogues.get(&screen.dialogue) else {
+            return;
+        };
+        let Some(node) = screen.node.as_ref().and_then(|id| dialogue.nodes.get(id)) else {
+            return;
+        };
+
+        let page_len = text.page(&screen, node).chars().count();
+
+        if (screen.revealed as usize) < page_len {
+            screen.revealed = page_len as f32;
+            return;
+        }
+
+        if screen.page + 1 < node.pages.len() {
+            screen.page += 1;
+            screen.revealed = 0.0;
+            return;
+        }
+
+        if node.choices(&flags).next().is_some() {
+            return;
+        }
+
+        let next = node.next.clone();
+        screen.goto(entity, next, dialogue, &mut flags, &mut cmd);
+    }
+
+    fn choose(
+        In((entity, index)): In<(Entity, usize)>,
+        mut screen: Query<&mut DialogueScreen>,
+        dialogues: Res<Assets<Dialogue>>,
+        mut flags: ResMut<DialogueFlags>,
+        mut cmd: Commands,
+    ) {
+        let Ok(mut screen) = screen.get_mut(entity) else {
+            return;
+        };
+        let Some(dialogue) = dialogues.get(&screen.dialogue) else {
+            return;
+        };
+        let Some(choice) = screen
+            .node
+            .as_ref()
+            .and_then(|id| dialogue.nodes.get(id))
+            .and_then(|node| node.choices(&flags).nth(index))
+        else {
+            return;
+        };
+
+        let (set_flag, next) = (choice.set_flag.clone(), choice.next.clone());
+        if let Some(flag) = set_flag {
+            flags.insert(flag);
+        }
+
+        screen.goto(entity, next, dialogue, &mut flags, &mut cmd);
+    }
+
+    fn on_shown(
+        _: On<ScreenShown>,
+        mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+        mut state: ResMut<NextState<GameState>>,
+    ) {
+        state.set(GameState::Cutscene);
+        cursor.grab_mode = CursorGrabMode::None;
+        cursor.visible = true;
+    }
+
+    fn on_hidden(
+        _: On<ScreenHidden>,
+        mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+        mut state: ResMut<NextState<GameState>>,
+    ) {
+        state.set(GameState::InGame);
+        cursor.grab_mode = CursorGrabMode::Confined;
+        cursor.visible = false;
+    }
+}
@@ -9,18 +9,26 @@ use dreamseeker_util::{construct::Make, observers};
 use crate::{
     GameState,
     input::ui::{Confirm, actions},
-    player::{Player, item::Token},
+    locale::LocalizedText,
+    player::Player,
+    progress::{CollectibleKind, Progress},
+    save::SaveData,
     trigger::InitialSpawn,
 };
 
-use super::{Screen, ScreenCommandsExt, ScreenHidden, ScreenShown};
+use super::{
+    Screen, ScreenAnimation, ScreenCommandsExt, ScreenHidden, ScreenShown, pointer::ScreenClicked,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, EndScreen::update);
 }
 
 #[derive(Component, Reflect)]
-#[require(Screen)]
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
 pub struct EndScreen {
     color: Oklcha,
     timer: f32,
@@ -38,7 +46,7 @@ impl Default for EndScreen {
 impl EndScreen {
     pub fn bundle() -> impl Bundle {
         let title = (
-            Text::new("🎉🎉🎉 Winner! 🎉🎉🎉"),
+            LocalizedText::new("end.title"),
             TextFont {
                 font_size: 36.0,
                 ..default()
@@ -59,20 +67,30 @@ impl EndScreen {
             },
             BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.5)),
             actions(),
-            observers![Self::on_shown, Self::on_hidden, Self::on_confirm],
+            observers![
+                Self::on_shown,
+                Self::on_hidden,
+                Self::on_confirm,
+                Self::on_click
+            ],
             children![title, body],
         )
     }
 
-    fn make_body(player: Single<&Player>, tokens: Query<&Token>) -> Result<impl Bundle + use<>> {
-        let leftover = tokens.count();
-        let tokens = player.dream_tokens as usize;
-        let total = leftover + tokens;
+    fn make_body(progress: Res<Progress>, save: Res<SaveData>) -> Result<impl Bundle + use<>> {
+        let tokens = progress.count(CollectibleKind::Token, &save);
+
+        let key = if save.assisted {
+            "end.body_assisted"
+        } else {
+            "end.body"
+        };
 
         Ok((
-            Text::new(format!(
-                "You collected {tokens} / {total} tokens!\nPress Space / B to continue playing."
-            )),
+            LocalizedText::new(key)
+                .with("tokens", tokens.found)
+                .with("total", tokens.total)
+                .with("percent", format!("{:.0}", progress.completion(&save))),
             TextFont {
                 font_size: 30.0,
                 ..default()
@@ -101,22 +119,29 @@ impl EndScreen {
         cursor.visible = false;
     }
 
-    fn on_confirm(
-        event: On<Start<Confirm>>,
+    fn on_confirm(event: On<Start<Confirm>>, mut cmd: Commands) {
+        cmd.run_system_cached_with(Self::confirm, event.context);
+    }
+
+    fn on_click(event: On<ScreenClicked>, mut cmd: Commands) {
+        cmd.run_system_cached_with(Self::confirm, event.0);
+    }
+
+    fn confirm(
+        In(entity): In<Entity>,
         screen: Query<&EndScreen>,
         mut player: Single<&mut Position, With<Player>>,
         spawn: Query<&Transform, With<InitialSpawn>>,
         mut cmd: Commands,
-    ) -> Result {
-        if screen.get(event.context)?.timer > 0.0 {
-            return Ok(());
+    ) {
+        if !screen.get(entity).is_ok_and(|screen| screen.timer <= 0.0) {
+            return;
         }
 
         if let Some(transform) = spawn.iter().next() {
             player.0 = transform.translation;
         }
         cmd.pop_screen();
-        Ok(())
     }
 
     // fn on_new_game(
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::player::Player;
+use crate::{locale::LocalizedText, player::Player};
 
 use super::Screen;
 
@@ -9,13 +9,16 @@ pub(super) fn plugin(app: &mut App) {
 }
 
 #[derive(Component)]
-#[require(Screen)]
+#[require(Screen {
+    priority: Screen::BACKGROUND,
+    ..default()
+})]
 pub struct HudScreen;
 
 impl HudScreen {
     pub fn bundle() -> impl Bundle {
         let tokens = (
-            Text::new("0 tokens"),
+            LocalizedText::new("hud.tokens").with("count", 0),
             TextFont::from_font_size(20.0),
             TokenCounter,
         );
@@ -37,9 +40,12 @@ impl HudScreen {
 struct TokenCounter;
 
 impl TokenCounter {
-    fn update(text: Query<(&mut Text, &TokenCounter)>, player: Single<&Player, Changed<Player>>) {
-        for (mut text, _) in text {
-            text.0 = format!("{} tokens", player.dream_tokens);
+    fn update(
+        text: Query<&mut LocalizedText, With<TokenCounter>>,
+        player: Single<&Player, Changed<Player>>,
+    ) {
+        for mut text in text {
+            text.set("count", player.dream_tokens);
         }
     }
 }
@@ -1,17 +1,23 @@
 use bevy::prelude::*;
 
-use super::Screen;
+use crate::locale::LocalizedText;
+
+use super::{Screen, ScreenAnimation};
 
 pub(super) fn plugin(_app: &mut App) {}
 
 #[derive(Component)]
-#[require(Screen)]
+#[require(Screen {
+    priority: Screen::POPUP,
+    overlay: true,
+    animation: ScreenAnimation::SlideUp,
+})]
 pub struct InfoScreen;
 
 impl InfoScreen {
-    pub fn bundle(text: String) -> impl Bundle {
+    pub fn bundle(text: LocalizedText) -> impl Bundle {
         let text = (
-            Text::new(text),
+            text,
             TextFont::from_font_size(20.0),
             TextLayout::new_with_justify(Justify::Center),
         );
@@ -4,10 +4,11 @@ use dreamseeker_util::observers;
 
 use crate::{
     input::ui::{Confirm, actions},
+    locale::LocalizedText,
     player::item::Item,
 };
 
-use super::{Screen, ScreenCommandsExt};
+use super::{Screen, ScreenAnimation, ScreenCommandsExt, pointer::ScreenClicked};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, ItemDescriptionScreen::update);
@@ -15,7 +16,7 @@ pub(super) fn plugin(app: &mut App) {
 
 pub fn item_description(item: Item) -> impl Bundle {
     let name = (
-        Text::new(item.name()),
+        LocalizedText::new(item.name_key()),
         TextFont {
             font_size: 36.0,
             ..default()
@@ -24,7 +25,7 @@ pub fn item_description(item: Item) -> impl Bundle {
     );
 
     let description = (
-        Text::new(item.description()),
+        LocalizedText::new(item.description_key()),
         TextFont {
             font_size: 24.0,
             ..default()
@@ -33,7 +34,7 @@ pub fn item_description(item: Item) -> impl Bundle {
     );
 
     let exit = (
-        Text::new("Press Space / B to exit"),
+        LocalizedText::new("item.exit"),
         TextFont {
             font_size: 24.0,
             ..default()
@@ -63,28 +64,39 @@ pub fn item_description(item: Item) -> impl Bundle {
         },
         BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.5)),
         actions(),
-        observers![ItemDescriptionScreen::on_confirm],
+        observers![
+            ItemDescriptionScreen::on_confirm,
+            ItemDescriptionScreen::on_click
+        ],
         children![column],
     )
 }
 
 #[derive(Component)]
-#[require(Screen)]
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
 pub struct ItemDescriptionScreen {
     cooldown: f32,
 }
 
 impl ItemDescriptionScreen {
-    fn on_confirm(
-        event: On<Start<Confirm>>,
-        screen: Query<&ItemDescriptionScreen>,
-        mut cmd: Commands,
-    ) -> Result {
-        let screen = screen.get(event.context)?;
-        if screen.cooldown <= 0.0 {
+    fn on_confirm(event: On<Start<Confirm>>, mut cmd: Commands) {
+        cmd.run_system_cached_with(Self::close, event.context);
+    }
+
+    fn on_click(event: On<ScreenClicked>, mut cmd: Commands) {
+        cmd.run_system_cached_with(Self::close, event.0);
+    }
+
+    fn close(In(entity): In<Entity>, screen: Query<&ItemDescriptionScreen>, mut cmd: Commands) {
+        if screen
+            .get(entity)
+            .is_ok_and(|screen| screen.cooldown <= 0.0)
+        {
             cmd.pop_screen();
         }
-        Ok(())
     }
 
     fn update(screen: Query<&mut ItemDescriptionScreen>, time: Res<Time>) {
@@ -0,0 +1,407 @@
+use bevy::{
+    camera::{RenderTarget, ScalingMode, primitives::Aabb},
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+    render::render_resource::TextureFormat,
+    scene::{SceneInstance, SceneSpawner},
+    window::{CursorGrabMode, CursorOptions, PrimaryWindow},
+};
+use dreamseeker_util::{construct::Make, observers};
+
+use crate::{
+    GameState, MainScene,
+    input::ui::actions,
+    locale::LocalizedText,
+    player::{Die, Player},
+    progress::{CollectibleKind, Progress},
+    save::SaveData,
+    trigger::Checkpoint,
+};
+
+use super::{
+    Screen, ScreenAnimation, ScreenCommandsExt, ScreenHidden, ScreenShown,
+    teleport::TeleportScreen,
+    widget::{Activate, AutoFocus, FocusChanged, Focusable},
+};
+
+const CHECKPOINT_COLOR: Color = Color::srgb(0.3, 0.7, 1.0);
+const PLAYER_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+const TOKEN_COLOR: Color = Color::srgb(0.2, 0.5, 1.0);
+const MISSING_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.3);
+
+/// Size of the longest side of a captured map, in pixels
+const CAPTURE_SIZE: f32 = 1024.0;
+/// Frames the capture camera stays around, so it renders at least once
+const CAPTURE_FRAMES: u8 = 3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            MapScreen::update,
+            WorldMap::capture,
+            MapCaptureCamera::update,
+        ),
+    );
+}
+
+/// The top-down map image of the level, covering the area of this entity's scale on
+/// the X and Z axes, like a cube empty in Blender. The top of the image is -Z
+///
+/// Without an image, the level is captured from above once it has loaded, and the
+/// transform is fitted to the bounds of its meshes
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component, Default)]
+#[require(Transform)]
+#[component(on_add)]
+pub struct WorldMap {
+    /// Asset path of an authored image, empty to capture the level instead
+    pub image: String,
+}
+
+/// The image shown by a [`WorldMap`], once there is one
+#[derive(Component, Clone)]
+pub struct MapImage(pub Handle<Image>);
+
+/// Triggered on a [`WorldMap`] once its capture has rendered
+#[derive(EntityEvent, Clone)]
+pub struct MapCaptured(pub Entity);
+
+/// Renders the capture of a [`WorldMap`], then goes away
+#[derive(Component)]
+struct MapCaptureCamera {
+    map: Entity,
+    frames: u8,
+}
+
+impl WorldMap {
+    fn on_add(mut world: DeferredWorld, ctx: HookContext) {
+        let path = world.get::<WorldMap>(ctx.entity).unwrap().image.clone();
+        if path.is_empty() {
+            return;
+        }
+
+        let image = world.load_asset(path);
+        world.commands().entity(ctx.entity).insert(MapImage(image));
+    }
+
+    /// Fits the map to the level and renders it with an orthographic camera looking down
+    fn capture(
+        maps: Query<(Entity, &WorldMap), Without<MapImage>>,
+        scene: Single<(Entity, &SceneInstance), With<MainScene>>,
+        spawner: Res<SceneSpawner>,
+        children: Query<&Children>,
+        meshes: Query<(Option<&Aabb>, &GlobalTransform), With<Mesh3d>>,
+        mut images: ResMut<Assets<Image>>,
+        mut cmd: Commands,
+    ) {
+        let Some((entity, _)) = maps.iter().find(|(_, map)| map.image.is_empty()) else {
+            return;
+        };
+        let (scene, instance) = *scene;
+        if !spawner.instance_is_ready(**instance) {
+            return;
+        }
+
+        let mut min = Vec3::MAX;
+        let mut max = Vec3::MIN;
+        for (aabb, transform) in meshes.iter_many(children.iter_descendants(scene)) {
+            // Bounds are calculated the frame after a mesh spawns
+            let Some(aabb) = aabb else {
+                return;
+            };
+
+            let affine = transform.affine();
+            let center = Vec3::from(affine.transform_point3a(aabb.center));
+            let half = Vec3::from(affine.matrix3.abs() * aabb.half_extents);
+            min = min.min(center - half);
+            max = max.max(center + half);
+        }
+        if min.cmpgt(max).any() {
+            return;
+        }
+
+        let size = (max - min).max(Vec3::splat(1.0));
+        let center = (min + max) / 2.0;
+
+        let pixels = (size.xz() / size.x.max(size.z) * CAPTURE_SIZE)
+            .ceil()
+            .as_uvec2();
+        let image = images.add(Image::new_target_texture(
+            pixels.x,
+            pixels.y,
+            TextureFormat::Rgba8UnormSrgb,
+            None,
+        ));
+
+        cmd.entity(entity).insert((
+            MapImage(image.clone()),
+            Transform::from_translation(center).with_scale(vec3(size.x, 1.0, size.z)),
+        ));
+
+        cmd.spawn((
+            MapCaptureCamera {
+                map: entity,
+                frames: CAPTURE_FRAMES,
+            },
+            Camera3d::default(),
+            Camera {
+                order: -1,
+                clear_color: ClearColorConfig::Custom(Color::NONE),
+                ..default()
+            },
+            RenderTarget::from(image),
+            Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::Fixed {
+                    width: size.x,
+                    height: size.z,
+                },
+                near: 0.0,
+                far: size.y + 2.0,
+                ..OrthographicProjection::default_3d()
+            }),
+            // Up on the image is -Z
+            Transform::from_xyz(center.x, max.y + 1.0, center.z)
+                .looking_to(Dir3::NEG_Y, Dir3::NEG_Z),
+        ));
+    }
+
+    /// Position on the image from 0 to 1, with y going down
+    fn project(transform: &GlobalTransform, pos: Vec3) -> Vec2 {
+        let center = transform.translation().xz();
+        let size = transform.scale().xz().abs().max(Vec2::splat(0.001));
+
+        ((pos.xz() - center) / size + 0.5).clamp(Vec2::ZERO, Vec2::ONE)
+    }
+}
+
+impl MapCaptureCamera {
+    fn update(cameras: Query<(Entity, &mut MapCaptureCamera)>, mut cmd: Commands) {
+        for (entity, mut camera) in cameras {
+            camera.frames = camera.frames.saturating_sub(1);
+            if camera.frames == 0 {
+                cmd.entity(entity).despawn();
+                cmd.trigger(MapCaptured(camera.map));
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
+pub struct MapScreen;
+
+/// The marker of an activated checkpoint
+#[derive(Component)]
+struct MapCheckpoint {
+    checkpoint: Entity,
+    name: String,
+}
+
+#[derive(Component)]
+struct SelectedName;
+
+impl MapScreen {
+    pub fn bundle() -> impl Bundle {
+        (
+            MapScreen,
+            Node {
+                width: percent(100),
+                height: percent(100),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: px(10),
+                ..default()
+            },
+            BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.8)),
+            actions(),
+            Make(Self::make),
+            observers![Self::on_shown, Self::on_hidden],
+        )
+    }
+
+    fn make(
+        map: Single<(&MapImage, &GlobalTransform)>,
+        checkpoints: Query<(Entity, &Checkpoint, &GlobalTransform)>,
+        player: Single<(&Player, &GlobalTransform)>,
+        progress: Res<Progress>,
+        save: Res<SaveData>,
+    ) -> Result<impl Bundle + use<>> {
+        let (MapImage(map_image), map_transform) = *map;
+        let (player, player_transform) = *player;
+
+        let mut checkpoints = checkpoints
+            .iter()
+            .filter(|(_, c, _)| c.checked)
+            .collect::<Vec<_>>();
+        checkpoints.sort_by(|a, b| a.1.id.cmp(&b.1.id));
+
+        // Missing tokens are only shown in areas the player explored
+        let tokens = progress
+            .collectibles()
+            .filter(|(_, c)| c.kind == CollectibleKind::Token)
+            .filter_map(|(id, c)| {
+                let found = save.found.contains(id);
+                let explored = c
+                    .area
+                    .as_deref()
+                    .is_some_and(|area| progress.explored(area, &save));
+
+                let color = match (found, explored) {
+                    (true, _) => TOKEN_COLOR,
+                    (false, true) => MISSING_COLOR,
+                    (false, false) => return None,
+                };
+
+                Some(marker(
+                    WorldMap::project(map_transform, c.position),
+                    8.0,
+                    color,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        // Scrolling goes through the checkpoints in order of their ids
+        let checkpoint_markers = checkpoints
+            .into_iter()
+            .map(|(entity, checkpoint, transform)| {
+                let uv = WorldMap::project(map_transform, transform.translation());
+                let bundle = (
+                    Focusable,
+                    MapCheckpoint {
+                        checkpoint: entity,
+                        name: checkpoint.id.clone(),
+                    },
+                    marker(uv, 16.0, CHECKPOINT_COLOR),
+                    observers![Self::on_activate],
+                );
+                (bundle, player.last_checkpoint == Some(entity))
+            })
+            .collect::<Vec<_>>();
+
+        let player_marker = marker(
+            WorldMap::project(map_transform, player_transform.translation()),
+            14.0,
+            PLAYER_COLOR,
+        );
+
+        let size = map_transform.scale().xz().abs();
+        let image = (
+            ImageNode::new(map_image.clone()),
+            Node {
+                height: percent(75),
+                aspect_ratio: Some(size.x / size.y.max(0.001)),
+                ..default()
+            },
+            Outline::new(px(1), px(0), Color::WHITE),
+            Children::spawn((
+                SpawnIter(tokens.into_iter()),
+                SpawnWith(move |parent: &mut ChildSpawner| {
+                    for (bundle, last) in checkpoint_markers {
+                        let mut marker = parent.spawn(bundle);
+                        if last {
+                            marker.insert(AutoFocus);
+                        }
+                    }
+                }),
+                Spawn(player_marker),
+            )),
+        );
+
+        let name = (SelectedName, LocalizedText::new("teleport.none"));
+        let help = (
+            LocalizedText::new("map.help"),
+            TextFont::from_font_size(20.0),
+            TextLayout::new_with_justify(Justify::Center),
+        );
+
+        Ok(children![image, name, help])
+    }
+
+    fn update(
+        screens: FocusChanged<MapScreen>,
+        children: Query<&Children>,
+        checkpoints: Query<&MapCheckpoint>,
+        mut name: Query<&mut LocalizedText, With<SelectedName>>,
+    ) {
+        for (entity, focus) in screens {
+            for child in children.iter_descendants(entity) {
+                if let Ok(mut text) = name.get_mut(child) {
+                    *text = match focus.0.and_then(|e| checkpoints.get(e).ok()) {
+                        Some(checkpoint) => {
+                            LocalizedText::new("teleport.selected").with("name", &checkpoint.name)
+                        }
+                        None => LocalizedText::new("teleport.none"),
+                    };
+                }
+            }
+        }
+    }
+
+    fn on_activate(
+        event: On<Activate>,
+        checkpoints: Query<&MapCheckpoint>,
+        mut player: Single<(Entity, &mut Player)>,
+        mut cmd: Commands,
+    ) -> Result {
+        player.1.last_checkpoint = Some(checkpoints.get(event.0)?.checkpoint);
+
+        cmd.pop_screen();
+        cmd.trigger(Die(player.0));
+        Ok(())
+    }
+
+    fn on_shown(
+        _: On<ScreenShown>,
+        mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+        mut state: ResMut<NextState<GameState>>,
+    ) {
+        state.set(GameState::Paused);
+        cursor.grab_mode = CursorGrabMode::None;
+        cursor.visible = true;
+    }
+
+    fn on_hidden(
+        _: On<ScreenHidden>,
+        mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+        mut state: ResMut<NextState<GameState>>,
+    ) {
+        state.set(GameState::InGame);
+        cursor.grab_mode = CursorGrabMode::Confined;
+        cursor.visible = false;
+    }
+}
+
+/// Opens the map, or the list of checkpoints in levels without a map image
+pub fn open_teleport(map: Query<(), With<MapImage>>, mut cmd: Commands) {
+    if map.is_empty() {
+        cmd.push_screen(TeleportScreen::bundle());
+    } else {
+        cmd.push_screen(MapScreen::bundle());
+    }
+}
+
+/// A square centered on `uv`
+fn marker(uv: Vec2, size: f32, color: Color) -> impl Bundle {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            left: percent(uv.x * 100.0),
+            top: percent(uv.y * 100.0),
+            width: px(size),
+            height: px(size),
+            margin: UiRect {
+                left: px(-size / 2.0),
+                top: px(-size / 2.0),
+                ..default()
+            },
+            ..default()
+        },
+        BackgroundColor(color),
+        Outline::new(px(1), px(0), Color::BLACK),
+    )
+}
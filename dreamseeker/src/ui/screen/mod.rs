@@ -1,24 +1,53 @@
 use bevy::{ecs::entity_disabling::Disabled, prelude::*};
+use bevy_enhanced_input::prelude::ContextActivity;
 
+pub mod achievements;
+pub mod assist;
+pub mod controls;
+pub mod dialogue;
 pub mod end;
 pub mod hud;
 pub mod info;
 pub mod item;
+pub mod map;
 pub mod pause;
+pub mod pointer;
+pub mod settings;
+pub mod stats;
 pub mod teleport;
+pub mod title;
+pub mod widget;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
+        self::achievements::plugin,
+        self::assist::plugin,
+        self::controls::plugin,
+        self::dialogue::plugin,
         self::end::plugin,
         self::hud::plugin,
         self::info::plugin,
         self::item::plugin,
+        self::map::plugin,
         self::pause::plugin,
+    ))
+    .add_plugins((
+        self::pointer::plugin,
+        self::settings::plugin,
+        self::stats::plugin,
         self::teleport::plugin,
+        self::title::plugin,
+        self::widget::plugin,
     ))
-    .init_resource::<ScreenStack>();
+    .init_resource::<ScreenStack>()
+    .add_systems(Update, ScreenTransition::update);
 }
 
+/// How long screens take to animate in and out, in seconds
+const ANIMATION_TIME: f32 = 0.15;
+
+/// Open screens from the bottom up, ordered by priority and then by when they were pushed.
+/// The top one takes input
 #[derive(Resource, Reflect, Clone, Default)]
 pub struct ScreenStack(Vec<Entity>);
 
@@ -26,44 +55,205 @@ impl ScreenStack {
     pub fn current(&self) -> Option<Entity> {
         self.0.last().copied()
     }
+
+    /// Open screens from the top down
+    pub fn iter(&self) -> impl Iterator<Item = Entity> {
+        self.0.iter().rev().copied()
+    }
+
+    /// Hides every screen under one that isn't an overlay, and lets only the top one take
+    /// input. Triggers [`ScreenShown`] if the top screen is not `previous` anymore
+    fn refresh(world: &mut World, previous: Option<Entity>) {
+        let stack = world.resource::<ScreenStack>().0.clone();
+        let current = stack.last().copied();
+
+        let mut covered = false;
+        for (i, &entity) in stack.iter().enumerate().rev() {
+            let overlay = world.get::<Screen>(entity).is_some_and(|s| s.overlay);
+
+            let mut screen = world.entity_mut(entity);
+            if covered {
+                screen.insert_recursive::<Children>(Disabled);
+            } else {
+                screen.remove_recursive::<Children, Disabled>();
+            }
+            screen.insert((
+                ZIndex(i as i32),
+                ContextActivity::<Screen>::new(Some(entity) == current),
+            ));
+
+            covered |= !overlay;
+        }
+
+        if let Some(current) = current
+            && Some(current) != previous
+        {
+            world.trigger(ScreenShown(current));
+        }
+    }
+
+    /// Takes `entity` off the stack, letting it animate out before it is despawned
+    fn remove(world: &mut World, entity: Entity) {
+        let previous = world.resource::<ScreenStack>().current();
+        let Some(index) = world
+            .resource::<ScreenStack>()
+            .0
+            .iter()
+            .position(|&e| e == entity)
+        else {
+            return;
+        };
+
+        world.trigger(ScreenHidden(entity));
+        world.resource_mut::<ScreenStack>().0.remove(index);
+
+        let Ok(mut screen) = world.get_entity_mut(entity) else {
+            Self::refresh(world, previous);
+            return;
+        };
+
+        let animated = screen
+            .get::<Screen>()
+            .is_some_and(|s| s.animation != ScreenAnimation::None);
+        if animated && !screen.contains::<Disabled>() {
+            screen.insert(ContextActivity::<Screen>::INACTIVE);
+            if let Some(mut transition) = screen.get_mut::<ScreenTransition>() {
+                transition.closing = true;
+            }
+        } else {
+            screen.despawn();
+        }
+
+        Self::refresh(world, previous);
+    }
+}
+
+/// How a screen moves in when it is pushed and out when it is popped
+#[derive(Reflect, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ScreenAnimation {
+    #[default]
+    None,
+    /// Grows out of the middle of the window
+    Zoom,
+    /// Slides up from the bottom of the window
+    SlideUp,
 }
 
 #[derive(Component, Reflect, Default)]
+#[require(Interaction, widget::Focus, ScreenTransition)]
 pub struct Screen {
+    /// Screens with a higher priority stay above ones that are pushed later
     pub priority: i32,
+    /// Keeps the screens below visible
+    pub overlay: bool,
+    pub animation: ScreenAnimation,
 }
 
-pub struct PushScreen<B>(pub B);
+impl Screen {
+    /// Always at the bottom, like the HUD
+    pub const BACKGROUND: i32 = -2;
+    /// Under every menu, like info popups
+    pub const POPUP: i32 = -1;
+}
+
+/// How far a screen is through its animation, from 0 when closed to 1 when open
+#[derive(Component, Default)]
+struct ScreenTransition {
+    progress: f32,
+    closing: bool,
+}
+
+impl ScreenTransition {
+    fn update(
+        screens: Query<(Entity, &Screen, &mut ScreenTransition)>,
+        time: Res<Time<Real>>,
+        mut cmd: Commands,
+    ) {
+        for (entity, screen, mut transition) in screens {
+            let step = time.delta_secs() / ANIMATION_TIME;
+
+            if transition.closing {
+                transition.progress -= step;
+                if transition.progress <= 0.0 {
+                    cmd.entity(entity).despawn();
+                    continue;
+                }
+            } else if transition.progress < 1.0 {
+                transition.progress = (transition.progress + step).min(1.0);
+            } else {
+                continue;
+            }
+
+            let t = transition.progress.clamp(0.0, 1.0);
+            let t = t * t * (3.0 - 2.0 * t);
+
+            let transform = match screen.animation {
+                ScreenAnimation::None => UiTransform::default(),
+                ScreenAnimation::Zoom => UiTransform {
+                    scale: Vec2::splat(0.9 + 0.1 * t),
+                    ..default()
+                },
+                ScreenAnimation::SlideUp => UiTransform {
+                    translation: Val2::percent(0.0, 100.0 * (1.0 - t)),
+                    ..default()
+                },
+            };
+            cmd.entity(entity).insert(transform);
+        }
+    }
+}
+
+/// Inserts the bundle on an entity and puts it on the stack, for when the screen needs
+/// to be known before it is pushed
+pub struct PushScreen<B>(pub Entity, pub B);
 
 impl<B: Bundle> Command for PushScreen<B> {
     fn apply(self, world: &mut World) -> () {
-        if let Some(&e) = world.resource::<ScreenStack>().0.last() {
-            world.entity_mut(e).insert_recursive::<Children>(Disabled);
-        }
+        let previous = world.resource::<ScreenStack>().current();
 
-        let entity = world.spawn(self.0).id();
-        world.resource_mut::<ScreenStack>().0.push(entity);
-        world.trigger(ScreenShown(entity));
+        let entity = self.0;
+        let Ok(mut screen) = world.get_entity_mut(entity) else {
+            return;
+        };
+        screen.insert(self.1);
+        let priority = world.get::<Screen>(entity).map_or(0, |s| s.priority);
+
+        // Above every screen with the same priority or lower
+        let screens = world.resource::<ScreenStack>().0.clone();
+        let index = screens
+            .iter()
+            .rposition(|&e| {
+                world
+                    .get::<Screen>(e)
+                    .is_none_or(|s| s.priority <= priority)
+            })
+            .map_or(0, |i| i + 1);
+        world.resource_mut::<ScreenStack>().0.insert(index, entity);
+
+        ScreenStack::refresh(world, previous);
     }
 }
 
+/// Removes the top screen
 pub struct PopScreen;
 
 impl Command for PopScreen {
     fn apply(self, world: &mut World) -> () {
-        let Some(&entity) = world.resource::<ScreenStack>().0.last() else {
-            return;
-        };
-        world.trigger(ScreenHidden(entity));
-        world.resource_mut::<ScreenStack>().0.pop();
-        world.despawn(entity);
-        if let Some(&e) = world.resource::<ScreenStack>().0.last() {
-            world.entity_mut(e).remove_recursive::<Children, Disabled>();
-            world.trigger(ScreenShown(e));
+        if let Some(entity) = world.resource::<ScreenStack>().current() {
+            ScreenStack::remove(world, entity);
         }
     }
 }
 
+/// Removes a screen from anywhere in the stack
+pub struct CloseScreen(pub Entity);
+
+impl Command for CloseScreen {
+    fn apply(self, world: &mut World) {
+        ScreenStack::remove(world, self.0);
+    }
+}
+
 pub trait ScreenCommandsExt {
     fn push_screen(&mut self, bundle: impl Bundle);
     #[allow(dead_code)]
@@ -72,20 +262,28 @@ pub trait ScreenCommandsExt {
         self.push_screen(bundle);
     }
     fn pop_screen(&mut self);
+    fn close_screen(&mut self, entity: Entity);
 }
 
 impl ScreenCommandsExt for Commands<'_, '_> {
     fn push_screen(&mut self, bundle: impl Bundle) {
-        self.queue(PushScreen(bundle));
+        let entity = self.spawn_empty().id();
+        self.queue(PushScreen(entity, bundle));
     }
 
     fn pop_screen(&mut self) {
         self.queue(PopScreen);
     }
+
+    fn close_screen(&mut self, entity: Entity) {
+        self.queue(CloseScreen(entity));
+    }
 }
 
+/// Triggered on a screen when it is removed from the stack
 #[derive(EntityEvent, Clone)]
 pub struct ScreenHidden(pub Entity);
 
+/// Triggered on a screen when it becomes the top one
 #[derive(EntityEvent, Clone)]
 pub struct ScreenShown(pub Entity);
@@ -5,22 +5,146 @@ use bevy::{
 use dreamseeker_util::{construct::Make, observers};
 
 use crate::{
-    GameState,
+    GameState, MainScene,
+    cutscene::RunningCutscene,
     input::ui::actions,
-    player::item::{Item, PlayerItems},
+    locale::LocalizedText,
+    player::{
+        Die, Player,
+        camera::PlayerCamera,
+        item::{Item, PlayerItems},
+    },
+    progress::{AreaProgress, Progress},
+    save::SaveData,
 };
 
-use super::{Screen, ScreenHidden, ScreenShown};
+use super::{
+    Screen, ScreenAnimation, ScreenCommandsExt, ScreenHidden, ScreenShown, ScreenStack,
+    achievements::AchievementsScreen,
+    controls::ControlsScreen,
+    hud::HudScreen,
+    map::open_teleport,
+    settings::SettingsScreen,
+    stats::StatsScreen,
+    title::TitleScreen,
+    widget::{Activate, AutoFocus, button, list},
+};
+
+const TAB_COLOR: Color = Color::srgb(1.0, 0.8, 0.4);
 
-pub(super) fn plugin(_app: &mut App) {}
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, PauseScreen::update);
+}
 
 #[derive(Component, Reflect)]
-#[require(Screen)]
-pub struct PauseScreen;
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
+pub struct PauseScreen {
+    tab: PauseTab,
+}
+
+#[derive(Component, Reflect, Clone, Copy, PartialEq, Eq)]
+enum PauseTab {
+    Menu,
+    Items,
+}
+
+impl PauseTab {
+    const ALL: [Self; 2] = [Self::Menu, Self::Items];
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Menu => "pause.tab.menu",
+            Self::Items => "pause.tab.items",
+        }
+    }
+}
+
+/// The contents of a tab, only shown while it is selected
+#[derive(Component)]
+struct TabPanel(PauseTab);
+
+#[derive(Component, Clone, Copy)]
+enum PauseAction {
+    Resume,
+    Settings,
+    Controls,
+    Achievements,
+    Stats,
+    Restart,
+    Teleport,
+    Title,
+    Quit,
+}
+
+impl PauseAction {
+    const ALL: [Self; 9] = [
+        Self::Resume,
+        Self::Settings,
+        Self::Controls,
+        Self::Achievements,
+        Self::Stats,
+        Self::Restart,
+        Self::Teleport,
+        Self::Title,
+        Self::Quit,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Resume => "pause.resume",
+            Self::Settings => "pause.settings",
+            Self::Controls => "pause.controls",
+            Self::Achievements => "pause.achievements",
+            Self::Stats => "pause.stats",
+            Self::Restart => "pause.restart",
+            Self::Teleport => "pause.teleport",
+            Self::Title => "pause.title",
+            Self::Quit => "pause.quit",
+        }
+    }
+}
 
 impl PauseScreen {
     pub fn bundle() -> impl Bundle {
-        let list = (
+        let tabs = (
+            Node {
+                column_gap: px(20),
+                margin: UiRect::bottom(px(20)),
+                ..default()
+            },
+            Children::spawn(SpawnIter(PauseTab::ALL.into_iter().map(|tab| {
+                (
+                    button((
+                        tab,
+                        LocalizedText::new(tab.key()),
+                        TextFont::from_font_size(30.0),
+                    )),
+                    observers![Self::on_tab],
+                )
+            }))),
+        );
+
+        let menu = (
+            TabPanel(PauseTab::Menu),
+            list(),
+            Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+                for action in PauseAction::ALL {
+                    let mut entity = parent.spawn((
+                        button(LocalizedText::new(action.key())),
+                        action,
+                        observers![Self::on_action],
+                    ));
+                    if matches!(action, PauseAction::Resume) {
+                        entity.insert(AutoFocus);
+                    }
+                }
+            })),
+        );
+
+        let items = (
             Node {
                 flex_direction: FlexDirection::Column,
                 row_gap: px(10),
@@ -29,39 +153,35 @@ impl PauseScreen {
             Make(Self::make_entries),
         );
 
-        let body = (
+        let items = (
+            TabPanel(PauseTab::Items),
             Node {
                 width: percent(100),
                 padding: UiRect::horizontal(percent(20)),
                 flex_direction: FlexDirection::Row,
                 align_items: AlignItems::Center,
+                column_gap: px(40),
                 ..default()
             },
-            children![list],
-        );
-
-        let title = (
-            Text::new("Items"),
-            TextFont {
-                font_size: 36.0,
-                ..default()
-            },
-            TextLayout::new(Justify::Center, LineBreak::NoWrap),
+            children![items, Make(Self::make_progress)],
         );
 
         (
-            PauseScreen,
+            PauseScreen {
+                tab: PauseTab::Menu,
+            },
             Node {
                 width: percent(100),
                 height: percent(100),
                 flex_direction: FlexDirection::Column,
                 align_items: AlignItems::Center,
+                padding: UiRect::top(px(40)),
                 ..default()
             },
             BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.5)),
             actions(),
             observers![Self::on_shown, Self::on_hidden],
-            children![title, body],
+            children![tabs, menu, items],
         )
     }
 
@@ -74,6 +194,140 @@ impl PauseScreen {
         )))
     }
 
+    fn make_progress(progress: Res<Progress>, save: Res<SaveData>) -> Result<impl Bundle + use<>> {
+        let completion = (
+            LocalizedText::new("pause.completion")
+                .with("percent", format!("{:.0}", progress.completion(&save))),
+            TextFont::from_font_size(30.0),
+        );
+
+        let areas = progress.areas(&save).into_iter().map(|area| {
+            let AreaProgress { id, tokens, chests } = area;
+            let text = match id {
+                Some(id) => LocalizedText::new("pause.area").with("area", id),
+                None => LocalizedText::new("pause.area_other"),
+            };
+
+            (
+                text.with("tokens", tokens.found)
+                    .with("total_tokens", tokens.total)
+                    .with("chests", chests.found)
+                    .with("total_chests", chests.total),
+                TextFont::from_font_size(20.0),
+            )
+        });
+
+        Ok((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: px(10),
+                ..default()
+            },
+            Children::spawn((Spawn(completion), SpawnIter(areas))),
+        ))
+    }
+
+    /// Shows the panel of the selected tab and highlights its name
+    fn update(
+        screens: Query<(Entity, &PauseScreen), Changed<PauseScreen>>,
+        children: Query<&Children>,
+        mut panels: Query<(&TabPanel, &mut Node, &mut Visibility)>,
+        mut tabs: Query<(&PauseTab, &mut TextColor)>,
+    ) {
+        for (entity, screen) in screens {
+            for child in children.iter_descendants(entity) {
+                if let Ok((panel, mut node, mut visibility)) = panels.get_mut(child) {
+                    let shown = panel.0 == screen.tab;
+                    node.display = if shown { Display::Flex } else { Display::None };
+                    *visibility = if shown {
+                        Visibility::Inherited
+                    } else {
+                        Visibility::Hidden
+                    };
+                }
+
+                if let Ok((tab, mut color)) = tabs.get_mut(child) {
+                    color.0 = if *tab == screen.tab {
+                        TAB_COLOR
+                    } else {
+                        Color::WHITE
+                    };
+                }
+            }
+        }
+    }
+
+    fn on_tab(
+        event: On<Activate>,
+        children: Query<&Children>,
+        tabs: Query<&PauseTab>,
+        mut screen: Single<&mut PauseScreen>,
+    ) {
+        let tab = children
+            .iter_descendants(event.0)
+            .find_map(|e| tabs.get(e).ok());
+
+        if let Some(&tab) = tab {
+            screen.tab = tab;
+        }
+    }
+
+    fn on_action(
+        event: On<Activate>,
+        actions: Query<&PauseAction>,
+        player: Single<Entity, With<Player>>,
+        mut exit: MessageWriter<AppExit>,
+        mut cmd: Commands,
+    ) -> Result {
+        match actions.get(event.0)? {
+            PauseAction::Resume => cmd.pop_screen(),
+            PauseAction::Settings => cmd.push_screen(SettingsScreen::bundle()),
+            PauseAction::Controls => cmd.push_screen(ControlsScreen::bundle()),
+            PauseAction::Achievements => cmd.push_screen(AchievementsScreen::bundle()),
+            PauseAction::Stats => cmd.push_screen(StatsScreen::bundle()),
+            PauseAction::Restart => {
+                cmd.pop_screen();
+                cmd.run_system_cached(RunningCutscene::stop_all);
+                cmd.trigger(Die(*player));
+            }
+            PauseAction::Teleport => {
+                cmd.pop_screen();
+                cmd.run_system_cached(open_teleport);
+            }
+            PauseAction::Title => cmd.run_system_cached(Self::return_to_title),
+            PauseAction::Quit => {
+                exit.write(AppExit::Success);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Closes every menu and reloads the level behind the title. The new level spawns a
+    /// new player, so the old one goes too. Activated checkpoints come back from the save
+    fn return_to_title(
+        stack: Res<ScreenStack>,
+        hud: Query<(), With<HudScreen>>,
+        scene: Single<Entity, With<MainScene>>,
+        players: Query<Entity, With<Player>>,
+        mut camera: Single<&mut PlayerCamera>,
+        mut cmd: Commands,
+    ) {
+        for screen in stack.iter().filter(|&e| !hud.contains(e)) {
+            cmd.close_screen(screen);
+        }
+
+        for player in &players {
+            cmd.entity(player).despawn();
+        }
+        camera.reset();
+        cmd.run_system_cached(RunningCutscene::stop_all);
+
+        cmd.entity(*scene).despawn();
+        cmd.spawn(MainScene::bundle());
+        cmd.push_screen(TitleScreen::bundle());
+    }
+
     fn on_shown(
         _: On<ScreenShown>,
         mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
@@ -100,10 +354,13 @@ struct ItemEntry;
 
 impl ItemEntry {
     fn bundle(item: Item) -> impl Bundle {
-        let name = (Text::new(item.name()), TextFont::from_font_size(30.0));
+        let name = (
+            LocalizedText::new(item.name_key()),
+            TextFont::from_font_size(30.0),
+        );
 
         let desc = (
-            Text::new(item.description()),
+            LocalizedText::new(item.description_key()),
             TextFont {
                 font_size: 24.0,
                 ..default()
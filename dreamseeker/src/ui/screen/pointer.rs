@@ -0,0 +1,65 @@
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    ui::UiSystems,
+};
+
+use super::{Screen, ScreenStack};
+
+const PIXELS_PER_LINE: f32 = 20.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(PreUpdate, (click, scroll).after(UiSystems::Focus));
+}
+
+/// Triggered on a screen when it is clicked anywhere but its widgets
+#[derive(EntityEvent, Clone)]
+pub struct ScreenClicked(pub Entity);
+
+/// Triggered on the top screen for every line the mouse wheel scrolls, positive is up
+#[derive(EntityEvent, Clone)]
+pub struct ScreenScrolled {
+    pub entity: Entity,
+    pub lines: i32,
+}
+
+fn click(
+    screens: Query<&Interaction, (With<Screen>, Changed<Interaction>)>,
+    stack: Res<ScreenStack>,
+    mut cmd: Commands,
+) {
+    // Only the top screen takes clicks, the HUD below covers the whole window too
+    if let Some(screen) = stack.current()
+        && matches!(screens.get(screen), Ok(Interaction::Pressed))
+    {
+        cmd.trigger(ScreenClicked(screen));
+    }
+}
+
+fn scroll(
+    mut wheel: MessageReader<MouseWheel>,
+    mut scrolled: Local<f32>,
+    stack: Res<ScreenStack>,
+    mut cmd: Commands,
+) {
+    // Touchpads scroll in small steps, which add up to lines
+    *scrolled += wheel
+        .read()
+        .map(|w| match w.unit {
+            MouseScrollUnit::Line => w.y,
+            MouseScrollUnit::Pixel => w.y / PIXELS_PER_LINE,
+        })
+        .sum::<f32>();
+
+    let lines = scrolled.trunc();
+    *scrolled -= lines;
+
+    if lines != 0.0
+        && let Some(screen) = stack.current()
+    {
+        cmd.trigger(ScreenScrolled {
+            entity: screen,
+            lines: lines as i32,
+        });
+    }
+}
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+use dreamseeker_util::{construct::Make, observers};
+
+use crate::{
+    GameState,
+    input::ui::actions,
+    locale::{Language, Locale, LocalizedText},
+    player::camera::PlayerCamera,
+};
+
+use super::{
+    Screen, ScreenAnimation, ScreenCommandsExt, ScreenShown,
+    assist::AssistScreen,
+    widget::{Activate, AutoFocus, Slider, Toggle, WidgetChanged, button, list, toggle},
+};
+
+pub(super) fn plugin(_app: &mut App) {}
+
+#[derive(Component)]
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
+pub struct SettingsScreen;
+
+#[derive(Component)]
+struct LanguageOption;
+
+#[derive(Component)]
+struct StickZoomOption;
+
+#[derive(Component)]
+struct AutoFollowOption;
+
+impl SettingsScreen {
+    pub fn bundle() -> impl Bundle {
+        let title = (
+            LocalizedText::new("settings.title"),
+            TextFont::from_font_size(36.0),
+        );
+
+        let assist = (
+            button(LocalizedText::new("settings.assist")),
+            observers![Self::on_assist],
+        );
+
+        let help = (
+            LocalizedText::new("settings.help"),
+            TextFont::from_font_size(20.0),
+        );
+
+        (
+            Self,
+            Node {
+                width: percent(100),
+                height: percent(100),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: px(20),
+                ..default()
+            },
+            BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.8)),
+            actions(),
+            observers![Self::on_shown],
+            children![
+                title,
+                (
+                    list(),
+                    children![
+                        Make(Self::make_language),
+                        Make(Self::make_stick_zoom),
+                        Make(Self::make_auto_follow),
+                        assist
+                    ]
+                ),
+                help
+            ],
+        )
+    }
+
+    fn make_language(locale: Res<Locale>) -> Result<impl Bundle + use<>> {
+        let label =
+            LocalizedText::new("settings.language").with("language", locale.language.native_name());
+
+        Ok((
+            LanguageOption,
+            Slider {
+                value: Language::ALL
+                    .iter()
+                    .position(|&l| l == locale.language)
+                    .unwrap_or(0) as f32,
+                min: 0.0,
+                max: (Language::ALL.len() - 1) as f32,
+                step: 1.0,
+            },
+            AutoFocus,
+            label,
+            Node {
+                padding: UiRect::all(px(5)),
+                ..default()
+            },
+            observers![Self::on_language],
+        ))
+    }
+
+    fn on_language(
+        event: On<WidgetChanged>,
+        mut option: Query<(&Slider, &mut LocalizedText), With<LanguageOption>>,
+        mut locale: ResMut<Locale>,
+    ) -> Result {
+        let (slider, mut text) = option.get_mut(event.0)?;
+        let Some(&language) = Language::ALL.get(slider.value as usize) else {
+            return Ok(());
+        };
+
+        locale.language = language;
+        text.set("language", language.native_name());
+        Ok(())
+    }
+
+    fn make_stick_zoom(camera: Single<&PlayerCamera>) -> Result<impl Bundle + use<>> {
+        Ok((
+            StickZoomOption,
+            toggle(LocalizedText::new("settings.stick_zoom"), camera.stick_zoom),
+            observers![Self::on_stick_zoom],
+        ))
+    }
+
+    fn on_stick_zoom(
+        event: On<WidgetChanged>,
+        option: Query<&Toggle, With<StickZoomOption>>,
+        mut camera: Single<&mut PlayerCamera>,
+    ) -> Result {
+        camera.stick_zoom = option.get(event.0)?.0;
+        Ok(())
+    }
+
+    fn make_auto_follow(camera: Single<&PlayerCamera>) -> Result<impl Bundle + use<>> {
+        Ok((
+            AutoFollowOption,
+            toggle(
+                LocalizedText::new("settings.auto_follow"),
+                camera.auto_follow,
+            ),
+            observers![Self::on_auto_follow],
+        ))
+    }
+
+    fn on_auto_follow(
+        event: On<WidgetChanged>,
+        option: Query<&Toggle, With<AutoFollowOption>>,
+        mut camera: Single<&mut PlayerCamera>,
+    ) -> Result {
+        camera.auto_follow = option.get(event.0)?.0;
+        Ok(())
+    }
+
+    fn on_assist(_: On<Activate>, mut cmd: Commands) {
+        cmd.push_screen(AssistScreen::bundle());
+    }
+
+    fn on_shown(_: On<ScreenShown>, mut state: ResMut<NextState<GameState>>) {
+        state.set(GameState::Paused);
+    }
+}
@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+use dreamseeker_util::{construct::Make, observers};
+
+use crate::{
+    GameState, achievement::PlayEvent, input::ui::actions, locale::LocalizedText, save::SaveData,
+};
+
+use super::{Screen, ScreenAnimation, ScreenShown};
+
+/// Counters listed on the screen, each with a `stats.<name>` string that shows its `{value}`
+const COUNTERS: [(PlayEvent, &str); 10] = [
+    (PlayEvent::GroundJump, "ground_jumps"),
+    (PlayEvent::CoyoteTimeJump, "coyote_time_jumps"),
+    (PlayEvent::CoyoteFrictionJump, "coyote_friction_jumps"),
+    (PlayEvent::AirJump, "air_jumps"),
+    (PlayEvent::Dash, "dashes"),
+    (PlayEvent::Slide, "slides"),
+    (PlayEvent::Slam, "slams"),
+    (PlayEvent::WallGrab, "wall_grabs"),
+    (PlayEvent::Pogo, "pogos"),
+    (PlayEvent::Death, "deaths"),
+];
+
+pub(super) fn plugin(_app: &mut App) {}
+
+#[derive(Component)]
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
+pub struct StatsScreen;
+
+impl StatsScreen {
+    pub fn bundle() -> impl Bundle {
+        let title = (
+            LocalizedText::new("stats.title"),
+            TextFont::from_font_size(36.0),
+        );
+
+        let help = (
+            LocalizedText::new("settings.help"),
+            TextFont::from_font_size(20.0),
+        );
+
+        let columns = (
+            Node {
+                column_gap: px(60),
+                ..default()
+            },
+            children![Make(Self::make_counters), Make(Self::make_deaths)],
+        );
+
+        (
+            Self,
+            Node {
+                width: percent(100),
+                height: percent(100),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: px(20),
+                ..default()
+            },
+            BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.8)),
+            actions(),
+            observers![Self::on_shown],
+            children![title, columns, help],
+        )
+    }
+
+    fn make_counters(save: Res<SaveData>) -> Result<impl Bundle + use<>> {
+        let seconds = save.play_time as u32;
+        let play_time = format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+
+        let counters = COUNTERS
+            .into_iter()
+            .map(|(event, name)| {
+                LocalizedText::new(format!("stats.{name}")).with("value", save.count(event))
+            })
+            .chain([
+                LocalizedText::new("stats.distance").with("value", format!("{:.0}", save.distance)),
+                LocalizedText::new("stats.play_time").with("value", play_time),
+            ])
+            .map(|text| (text, TextFont::from_font_size(22.0)))
+            .collect::<Vec<_>>();
+
+        Ok((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: px(5),
+                ..default()
+            },
+            Children::spawn(SpawnIter(counters.into_iter())),
+        ))
+    }
+
+    /// Where the player died the most, from the top
+    fn make_deaths(save: Res<SaveData>) -> Result<impl Bundle + use<>> {
+        let title = (
+            LocalizedText::new("stats.deaths_by_location"),
+            TextFont::from_font_size(26.0),
+        );
+
+        let mut deaths = save.deaths.iter().collect::<Vec<_>>();
+        deaths.sort_by(|a, b| b.1.cmp(a.1));
+
+        let deaths = deaths
+            .into_iter()
+            .map(|(location, count)| {
+                (
+                    LocalizedText::new("stats.location")
+                        .with("location", location)
+                        .with("count", count),
+                    TextFont::from_font_size(20.0),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Ok((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: px(5),
+                ..default()
+            },
+            Children::spawn((Spawn(title), SpawnIter(deaths.into_iter()))),
+        ))
+    }
+
+    fn on_shown(_: On<ScreenShown>, mut state: ResMut<NextState<GameState>>) {
+        state.set(GameState::Paused);
+    }
+}
@@ -2,32 +2,45 @@ use bevy::{
     prelude::*,
     window::{CursorGrabMode, CursorOptions, PrimaryWindow},
 };
-use bevy_enhanced_input::prelude::Start;
 use dreamseeker_util::{construct::Make, observers};
 
 use crate::{
     GameState,
-    input::ui::{Confirm, Move, actions},
+    input::ui::actions,
+    locale::{LocalizedText, NoLocalize},
     player::{Die, Player},
     trigger::Checkpoint,
 };
 
-use super::{Screen, ScreenCommandsExt, ScreenHidden, ScreenShown};
+use super::{
+    Screen, ScreenAnimation, ScreenCommandsExt, ScreenHidden, ScreenShown,
+    widget::{Activate, FocusChanged, button, grid},
+};
+
+const COLUMNS: u16 = 4;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, TeleportScreen::update);
 }
 
 #[derive(Component, Reflect)]
-#[require(Screen)]
-pub struct TeleportScreen {
-    entries: Vec<(Entity, String)>,
-    selected: usize,
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
+pub struct TeleportScreen;
+
+/// A button for an activated checkpoint
+#[derive(Component)]
+struct TeleportEntry {
+    checkpoint: Entity,
+    name: String,
 }
 
 impl TeleportScreen {
     pub fn bundle() -> impl Bundle {
         (
+            TeleportScreen,
             Node {
                 width: percent(100),
                 height: percent(100),
@@ -38,12 +51,7 @@ impl TeleportScreen {
             BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.5)),
             actions(),
             Make(Self::make),
-            observers![
-                Self::on_confirm,
-                Self::on_shown,
-                Self::on_hidden,
-                Self::on_move
-            ],
+            observers![Self::on_shown, Self::on_hidden],
         )
     }
 
@@ -56,7 +64,7 @@ impl TeleportScreen {
 
         let selector = (
             Selector,
-            Text::new("None"),
+            LocalizedText::new("teleport.none"),
             Node {
                 padding: UiRect::all(px(10)),
                 ..default()
@@ -65,104 +73,56 @@ impl TeleportScreen {
         );
 
         let list = (
-            Node {
-                flex_direction: FlexDirection::Row,
-                flex_wrap: FlexWrap::Wrap,
-                ..default()
-            },
-            Children::spawn(SpawnIter(entries.clone().into_iter().map(|(_, name)| {
+            grid(COLUMNS),
+            Children::spawn(SpawnIter(entries.into_iter().map(|(checkpoint, name)| {
                 (
-                    Text::new(name),
-                    Outline::new(px(1), px(0), Color::WHITE),
-                    Node {
-                        padding: UiRect::all(px(5)),
-                        ..default()
-                    },
+                    button((Text::new(name.clone()), NoLocalize)),
+                    TeleportEntry { checkpoint, name },
+                    observers![Self::on_activate],
                 )
             }))),
         );
 
-        let info = (Text::new(
-            "Move left and right to select a checkpoint\nUse Space / A to teleport",
-        ),);
+        let info = LocalizedText::new("teleport.help");
 
-        let screen = TeleportScreen::new(entries);
-
-        Ok((screen, children![selector, list, info,]))
-    }
-
-    fn new(entries: Vec<(Entity, String)>) -> Self {
-        Self {
-            entries,
-            selected: 0,
-        }
+        Ok(children![selector, list, info])
     }
 
     fn update(
-        q: Query<(Entity, &TeleportScreen), Changed<TeleportScreen>>,
+        q: FocusChanged<TeleportScreen>,
         q_children: Query<&Children>,
-        mut selector: Query<&mut Text, With<Selector>>,
-    ) -> Result {
-        for (e, screen) in q {
+        entries: Query<&TeleportEntry>,
+        mut selector: Query<&mut LocalizedText, With<Selector>>,
+    ) {
+        for (e, focus) in q {
             for desc in q_children.iter_descendants(e) {
                 let Ok(mut text) = selector.get_mut(desc) else {
                     continue;
                 };
 
-                text.0 = if screen.entries.len() == 0 {
-                    format!("Selected: None")
-                } else {
-                    format!("Selected: {}", screen.entries[screen.selected].1)
+                *text = match focus.0.and_then(|entry| entries.get(entry).ok()) {
+                    None => LocalizedText::new("teleport.none"),
+                    Some(entry) => {
+                        LocalizedText::new("teleport.selected").with("name", &entry.name)
+                    }
                 };
             }
         }
-
-        Ok(())
     }
 
-    fn on_confirm(
-        event: On<Start<Confirm>>,
-        screen: Query<&TeleportScreen>,
+    fn on_activate(
+        event: On<Activate>,
+        entries: Query<&TeleportEntry>,
         mut player: Single<(Entity, &mut Player)>,
         mut cmd: Commands,
     ) -> Result {
-        let screen = screen.get(event.context)?;
-        let Some(&(entry, _)) = screen.entries.get(screen.selected) else {
-            return Ok(());
-        };
-
-        player.1.last_checkpoint = Some(entry);
+        player.1.last_checkpoint = Some(entries.get(event.0)?.checkpoint);
 
         cmd.pop_screen();
         cmd.trigger(Die(player.0));
         Ok(())
     }
 
-    fn on_move(event: On<Start<Move>>, mut screen: Query<&mut TeleportScreen>) -> Result {
-        let mut screen = screen.get_mut(event.context)?;
-
-        if screen.entries.is_empty() {
-            return Ok(());
-        }
-
-        let next = event.value.x > 0.0;
-
-        if next {
-            screen.selected += 1;
-            if screen.selected >= screen.entries.len() {
-                screen.selected = 0;
-            }
-        } else {
-            if screen.selected == 0 {
-                screen.selected = screen.entries.len() - 1;
-            } else {
-                screen.selected -= 1;
-            }
-        }
-
-        Ok(())
-    }
-
     fn on_shown(
         _: On<ScreenShown>,
         mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
@@ -0,0 +1,88 @@
+use bevy::{
+    prelude::*,
+    window::{CursorGrabMode, CursorOptions, PrimaryWindow},
+};
+use dreamseeker_util::observers;
+
+use crate::{GameState, input::ui::actions, locale::LocalizedText};
+
+use super::{
+    Screen, ScreenAnimation, ScreenCommandsExt, ScreenHidden, ScreenShown,
+    widget::{Activate, AutoFocus, button, list},
+};
+
+pub(super) fn plugin(_app: &mut App) {}
+
+/// Shown over the level when the game starts
+#[derive(Component)]
+#[require(Screen {
+    animation: ScreenAnimation::Zoom,
+    ..default()
+})]
+pub struct TitleScreen;
+
+impl TitleScreen {
+    pub fn bundle() -> impl Bundle {
+        let title = (
+            LocalizedText::new("title.name"),
+            TextFont::from_font_size(64.0),
+        );
+
+        let play = (
+            button(LocalizedText::new("title.play")),
+            AutoFocus,
+            observers![Self::on_play],
+        );
+
+        let quit = (
+            button(LocalizedText::new("title.quit")),
+            observers![Self::on_quit],
+        );
+
+        (
+            Self,
+            Node {
+                width: percent(100),
+                height: percent(100),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: px(40),
+                ..default()
+            },
+            BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.6)),
+            actions(),
+            observers![Self::on_shown, Self::on_hidden],
+            children![title, (list(), children![play, quit])],
+        )
+    }
+
+    fn on_play(_: On<Activate>, mut cmd: Commands) {
+        cmd.pop_screen();
+    }
+
+    fn on_quit(_: On<Activate>, mut exit: MessageWriter<AppExit>) {
+        exit.write(AppExit::Success);
+    }
+
+    /// The level keeps running behind the title, but the player can't move or pause
+    fn on_shown(
+        _: On<ScreenShown>,
+        mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+        mut state: ResMut<NextState<GameState>>,
+    ) {
+        state.set(GameState::Cutscene);
+        cursor.grab_mode = CursorGrabMode::None;
+        cursor.visible = true;
+    }
+
+    fn on_hidden(
+        _: On<ScreenHidden>,
+        mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+        mut state: ResMut<NextState<GameState>>,
+    ) {
+        state.set(GameState::InGame);
+        cursor.grab_mode = CursorGrabMode::Confined;
+        cursor.visible = false;
+    }
+}
@@ -0,0 +1,495 @@
+use bevy::{
+    prelude::*,
+    ui::{FocusPolicy, UiGlobalTransform, UiSystems},
+};
+use bevy_enhanced_input::prelude::Start;
+
+use crate::input::ui::{Confirm, Move};
+
+use super::{ScreenStack, pointer::ScreenScrolled};
+
+const FOCUS_COLOR: Color = Color::srgb(1.0, 0.8, 0.4);
+const TRACK_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.2);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(Focus::on_move)
+        .add_observer(Focus::on_confirm)
+        .add_observer(Focus::on_scroll)
+        .add_systems(PreUpdate, Focusable::interact.after(UiSystems::Focus))
+        .add_systems(
+            Update,
+            (
+                (Focus::init, Focus::ring).chain(),
+                Toggle::update,
+                Slider::update,
+            ),
+        );
+}
+
+/// The widget of a screen that takes [`Move`] and [`Confirm`]. Every screen has one,
+/// which starts on its [`AutoFocus`] widget, or else the first one in the tree
+#[derive(Component, Clone, Copy, PartialEq, Default, Debug)]
+pub struct Focus(pub Option<Entity>);
+
+/// Screens of type `S` whose [`Focus`] moved this frame
+pub type FocusChanged<'w, 's, S> =
+    Query<'w, 's, (Entity, &'static Focus), (With<S>, Changed<Focus>)>;
+
+/// Something on a screen that can be focused and activated. On its own it acts as a button
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component, Default)]
+#[require(Interaction, FocusPolicy::Block)]
+pub struct Focusable;
+
+/// Focuses this widget when its screen opens
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component, Default)]
+pub struct AutoFocus;
+
+/// A widget that flips between on and off when activated
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component, Default)]
+#[require(Focusable)]
+pub struct Toggle(pub bool);
+
+/// A widget whose value is stepped by moving left and right while it has focus.
+/// Activating it steps forward, wrapping back to `min` after `max`
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+#[require(Focusable)]
+pub struct Slider {
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+}
+
+#[derive(Component)]
+struct ToggleCheck;
+
+#[derive(Component)]
+struct SliderFill;
+
+/// Triggered on a widget when it is confirmed or clicked
+#[derive(EntityEvent, Clone)]
+pub struct Activate(pub Entity);
+
+/// Triggered on a [`Toggle`] or [`Slider`] after its value changed
+#[derive(EntityEvent, Clone)]
+pub struct WidgetChanged(pub Entity);
+
+/// A vertical list of widgets
+pub fn list() -> Node {
+    Node {
+        flex_direction: FlexDirection::Column,
+        row_gap: px(10),
+        ..default()
+    }
+}
+
+/// Widgets in rows of `columns`
+pub fn grid(columns: u16) -> Node {
+    Node {
+        display: Display::Grid,
+        grid_template_columns: RepeatedGridTrack::auto(columns),
+        row_gap: px(10),
+        column_gap: px(10),
+        ..default()
+    }
+}
+
+pub fn button(label: impl Bundle) -> impl Bundle {
+    (Focusable, widget_node(), Children::spawn(Spawn(label)))
+}
+
+pub fn toggle(label: impl Bundle, on: bool) -> impl Bundle {
+    let check = (
+        ToggleCheck,
+        Node {
+            width: px(16),
+            height: px(16),
+            ..default()
+        },
+        BackgroundColor(Color::NONE),
+        Outline::new(px(1), px(0), Color::WHITE),
+    );
+
+    (
+        Toggle(on),
+        widget_node(),
+        Children::spawn((Spawn(check), Spawn(label))),
+    )
+}
+
+pub fn slider(label: impl Bundle, slider: Slider) -> impl Bundle {
+    let track = (
+        Node {
+            width: px(120),
+            height: px(8),
+            ..default()
+        },
+        BackgroundColor(TRACK_COLOR),
+        children![(
+            SliderFill,
+            Node {
+                height: percent(100),
+                ..default()
+            },
+            BackgroundColor(Color::WHITE),
+        )],
+    );
+
+    (
+        slider,
+        widget_node(),
+        Children::spawn((Spawn(label), Spawn(track))),
+    )
+}
+
+fn widget_node() -> Node {
+    Node {
+        align_items: AlignItems::Center,
+        column_gap: px(10),
+        padding: UiRect::all(px(5)),
+        ..default()
+    }
+}
+
+impl Slider {
+    fn step_by(&mut self, dir: f32) {
+        self.value = (self.value + self.step * dir.signum()).clamp(self.min, self.max);
+    }
+
+    fn cycle(&mut self) {
+        if self.value >= self.max {
+            self.value = self.min;
+        } else {
+            self.step_by(1.0);
+        }
+    }
+
+    /// How far along the value is, from 0 to 1
+    fn fraction(&self) -> f32 {
+        if self.max > self.min {
+            ((self.value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    fn update(
+        sliders: Query<(Entity, &Slider), Changed<Slider>>,
+        children: Query<&Children>,
+        mut fills: Query<&mut Node, With<SliderFill>>,
+    ) {
+        for (entity, slider) in sliders {
+            for child in children.iter_descendants(entity) {
+                if let Ok(mut node) = fills.get_mut(child) {
+                    node.width = percent(slider.fraction() * 100.0);
+                }
+            }
+        }
+    }
+}
+
+impl Toggle {
+    fn update(
+        toggles: Query<(&Toggle, &Children), Changed<Toggle>>,
+        mut checks: Query<&mut BackgroundColor, With<ToggleCheck>>,
+    ) {
+        for (toggle, children) in toggles {
+            for &child in children {
+                if let Ok(mut color) = checks.get_mut(child) {
+                    color.0 = if toggle.0 { Color::WHITE } else { Color::NONE };
+                }
+            }
+        }
+    }
+}
+
+type Interacted = (With<Focusable>, Changed<Interaction>);
+
+impl Focusable {
+    fn interact(
+        widgets: Query<(Entity, &Interaction), Interacted>,
+        parents: Query<&ChildOf>,
+        mut screens: Query<&mut Focus>,
+        stack: Res<ScreenStack>,
+        mut cmd: Commands,
+    ) {
+        for (entity, interaction) in widgets {
+            if *interaction == Interaction::None {
+                continue;
+            }
+
+            let Some(mut focus) = parents
+                .iter_ancestors(entity)
+                .find(|&e| screens.contains(e))
+                .filter(|&e| stack.current() == Some(e))
+                .and_then(|e| screens.get_mut(e).ok())
+            else {
+                continue;
+            };
+
+            focus.set_if_neq(Focus(Some(entity)));
+
+            if *interaction == Interaction::Pressed {
+                cmd.run_system_cached_with(activate, entity);
+            }
+        }
+    }
+}
+
+impl Focus {
+    fn init(
+        screens: Query<(Entity, &mut Focus)>,
+        children: Query<&Children>,
+        widgets: Query<(Has<AutoFocus>, &InheritedVisibility), With<Focusable>>,
+    ) {
+        let visible = |e| widgets.get(e).is_ok_and(|(_, v)| v.get());
+
+        for (entity, mut focus) in screens {
+            if focus.0.is_some_and(visible) {
+                continue;
+            }
+
+            let mut tree = children
+                .iter_descendants_depth_first(entity)
+                .filter(|&e| visible(e))
+                .peekable();
+            let first = tree.peek().copied();
+            let auto = tree.find(|&e| widgets.get(e).is_ok_and(|(auto, _)| auto));
+
+            focus.set_if_neq(Focus(auto.or(first)));
+        }
+    }
+
+    /// Outlines the focused widget of every screen
+    fn ring(
+        screens: Query<(Entity, &Focus), Changed<Focus>>,
+        children: Query<&Children>,
+        widgets: Query<(), With<Focusable>>,
+        mut cmd: Commands,
+    ) {
+        for (entity, focus) in screens {
+            for widget in children.iter_descendants(entity) {
+                if !widgets.contains(widget) {
+                    continue;
+                }
+
+                let color = if focus.0 == Some(widget) {
+                    FOCUS_COLOR
+                } else {
+                    Color::NONE
+                };
+                cmd.entity(widget).insert(Outline::new(px(2), px(2), color));
+            }
+        }
+    }
+
+    fn on_move(
+        event: On<Start<Move>>,
+        mut screens: Query<&mut Focus>,
+        mut sliders: Query<&mut Slider>,
+        children: Query<&Children>,
+        widgets: Query<(&UiGlobalTransform, &InheritedVisibility), With<Focusable>>,
+        mut cmd: Commands,
+    ) {
+        let Ok(mut focus) = screens.get_mut(event.context) else {
+            return;
+        };
+
+        // Sliders take left and right for themselves
+        if let Some(current) = focus.0
+            && event.value.x != 0.0
+            && let Ok(mut slider) = sliders.get_mut(current)
+        {
+            slider.step_by(event.value.x);
+            cmd.trigger(WidgetChanged(current));
+            return;
+        }
+
+        let Some((from, _)) = focus.0.and_then(|e| widgets.get(e).ok()) else {
+            return;
+        };
+        // UI coordinates go down, input goes up
+        let Some(dir) = (event.value * vec2(1.0, -1.0)).try_normalize() else {
+            return;
+        };
+
+        let candidates = children
+            .iter_descendants(event.context)
+            .filter(|&e| Some(e) != focus.0)
+            .filter_map(|e| {
+                let (transform, visibility) = widgets.get(e).ok()?;
+                visibility
+                    .get()
+                    .then(|| (e, transform.translation - from.translation))
+            });
+
+        if let Some(next) = navigate(dir, candidates) {
+            focus.0 = Some(next);
+        }
+    }
+
+    fn on_confirm(event: On<Start<Confirm>>, screens: Query<&Focus>, mut cmd: Commands) {
+        if let Ok(Focus(Some(widget))) = screens.get(event.context) {
+            cmd.run_system_cached_with(activate, *widget);
+        }
+    }
+
+    /// Scrolling moves through the widgets in the order they are in the tree
+    fn on_scroll(
+        event: On<ScreenScrolled>,
+        mut screens: Query<&mut Focus>,
+        children: Query<&Children>,
+        widgets: Query<&InheritedVisibility, With<Focusable>>,
+    ) {
+        let Ok(mut focus) = screens.get_mut(event.entity) else {
+            return;
+        };
+
+        let tree = children
+            .iter_descendants_depth_first(event.entity)
+            .filter(|&e| widgets.get(e).is_ok_and(|v| v.get()))
+            .collect::<Vec<_>>();
+        let Some(current) = focus.0.and_then(|e| tree.iter().position(|&w| w == e)) else {
+            return;
+        };
+
+        let next = (current as i32 - event.lines).rem_euclid(tree.len() as i32);
+        focus.0 = Some(tree[next as usize]);
+    }
+}
+
+/// The closest widget roughly in `dir`, or else the furthest one back the other way
+fn navigate(dir: Vec2, candidates: impl Iterator<Item = (Entity, Vec2)>) -> Option<Entity> {
+    let mut ahead = None::<(Entity, f32)>;
+    let mut wrap = None::<(Entity, f32)>;
+
+    for (entity, offset) in candidates {
+        let Some(cos) = offset.try_normalize().map(|o| o.dot(dir)) else {
+            continue;
+        };
+
+        if cos > 0.5 {
+            let score = offset.length() / cos;
+            if ahead.is_none_or(|(_, best)| score < best) {
+                ahead = Some((entity, score));
+            }
+        } else if cos < -0.5 {
+            // Prefer the ones in line with the current widget
+            let score = offset.dot(dir) + offset.perp_dot(dir).abs();
+            if wrap.is_none_or(|(_, best)| score < best) {
+                wrap = Some((entity, score));
+            }
+        }
+    }
+
+    ahead.or(wrap).map(|(entity, _)| entity)
+}
+
+fn activate(
+    In(entity): In<Entity>,
+    mut toggles: Query<&mut Toggle>,
+    mut sliders: Query<&mut Slider>,
+    mut cmd: Commands,
+) {
+    if let Ok(mut toggle) = toggles.get_mut(entity) {
+        toggle.0 = !toggle.0;
+        cmd.trigger(WidgetChanged(entity));
+    }
+
+    if let Ok(mut slider) = sliders.get_mut(entity) {
+        slider.cycle();
+        cmd.trigger(WidgetChanged(entity));
+    }
+
+    cmd.trigger(Activate(entity));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entities<const N: usize>() -> [Entity; N] {
+        let mut world = World::new();
+        std::array::from_fn(|_| world.spawn_empty().id())
+    }
+
+    #[test]
+    fn navigates_to_the_closest_ahead() {
+        let [above, below, far_below, right] = entities();
+        let candidates = [
+            (above, vec2(0.0, -50.0)),
+            (below, vec2(0.0, 50.0)),
+            (far_below, vec2(0.0, 100.0)),
+            (right, vec2(200.0, 0.0)),
+        ];
+
+        assert_eq!(navigate(Vec2::Y, candidates.into_iter()), Some(below));
+        assert_eq!(navigate(Vec2::NEG_Y, candidates.into_iter()), Some(above));
+        assert_eq!(navigate(Vec2::X, candidates.into_iter()), Some(right));
+    }
+
+    #[test]
+    fn prefers_widgets_in_line() {
+        let [diagonal, straight] = entities();
+        let candidates = [(diagonal, vec2(40.0, 50.0)), (straight, vec2(0.0, 80.0))];
+
+        assert_eq!(navigate(Vec2::Y, candidates.into_iter()), Some(straight));
+    }
+
+    #[test]
+    fn wraps_to_the_far_end() {
+        let [first, second, beside] = entities();
+        let candidates = [
+            (first, vec2(0.0, -100.0)),
+            (second, vec2(0.0, -50.0)),
+            (beside, vec2(-120.0, -110.0)),
+        ];
+
+        // Nothing is below the last widget, so down goes back to the top
+        assert_eq!(navigate(Vec2::Y, candidates.into_iter()), Some(first));
+        assert_eq!(navigate(Vec2::Y, std::iter::empty()), None);
+    }
+
+    #[test]
+    fn sliders_step_within_their_range() {
+        let mut slider = Slider {
+            value: 0.5,
+            min: 0.0,
+            max: 1.0,
+            step: 0.25,
+        };
+
+        slider.step_by(1.0);
+        assert_eq!(slider.value, 0.75);
+        // Only the direction counts
+        slider.step_by(10.0);
+        assert_eq!(slider.value, 1.0);
+        slider.step_by(1.0);
+        assert_eq!(slider.value, 1.0);
+
+        slider.value = 0.1;
+        slider.step_by(-1.0);
+        assert_eq!(slider.value, 0.0);
+    }
+
+    #[test]
+    fn sliders_cycle_back_to_the_start() {
+        let mut slider = Slider {
+            value: 0.5,
+            min: 0.5,
+            max: 1.0,
+            step: 0.25,
+        };
+
+        slider.cycle();
+        assert_eq!(slider.value, 0.75);
+        slider.cycle();
+        assert_eq!(slider.value, 1.0);
+        slider.cycle();
+        assert_eq!(slider.value, 0.5);
+    }
+}
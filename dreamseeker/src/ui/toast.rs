@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+/// How long a toast stays up, in seconds
+const TOAST_TIME: f32 = 4.0;
+/// How long a toast takes to shrink away at the end, in seconds
+const SHRINK_TIME: f32 = 0.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, ToastArea::spawn)
+        .add_systems(Update, Toast::update);
+}
+
+/// The corner where toasts stack up, above every screen
+#[derive(Component)]
+#[require(
+    Node {
+        position_type: PositionType::Absolute,
+        top: px(20),
+        right: px(20),
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::End,
+        row_gap: px(10),
+        ..default()
+    },
+    GlobalZIndex(100),
+)]
+pub struct ToastArea;
+
+impl ToastArea {
+    fn spawn(mut cmd: Commands) {
+        cmd.spawn(ToastArea);
+    }
+}
+
+/// A short notification that goes away on its own. Spawn it as a child of the [`ToastArea`]
+#[derive(Component)]
+pub struct Toast {
+    timer: f32,
+}
+
+impl Toast {
+    pub fn bundle(content: impl Bundle) -> impl Bundle {
+        (
+            Self { timer: TOAST_TIME },
+            Node {
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(px(10)),
+                border: UiRect::all(px(1)),
+                ..default()
+            },
+            BorderColor::all(Color::WHITE),
+            BackgroundColor(Color::linear_rgba(0.0, 0.0, 0.0, 0.7)),
+            content,
+        )
+    }
+
+    /// Uses real time so toasts still go away while the game is paused
+    fn update(toasts: Query<(Entity, &mut Toast)>, mut cmd: Commands, time: Res<Time<Real>>) {
+        for (entity, mut toast) in toasts {
+            toast.timer -= time.delta_secs();
+
+            if toast.timer <= 0.0 {
+                cmd.entity(entity).despawn();
+            } else if toast.timer < SHRINK_TIME {
+                cmd.entity(entity).insert(UiTransform {
+                    scale: Vec2::splat(toast.timer / SHRINK_TIME),
+                    ..default()
+                });
+            }
+        }
+    }
+}
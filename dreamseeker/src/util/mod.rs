@@ -1 +1,2 @@
 pub mod angle;
+pub mod ron;
@@ -0,0 +1,566 @@
+//! Renders a top-down heatmap from the telemetry logs the game writes to `telemetry/`
+//! when it runs with `DREAMSEEKER_TELEMETRY` set. The folder is relative to where the game
+//! ran, so from the workspace root:
+//!
+//! ```text
+//! DREAMSEEKER_TELEMETRY=1 cargo run -p dreamseeker
+//! cargo run -p dreamseeker_heatmap -- telemetry --event death --out deaths.png
+//! ```
+//!
+//! The game also logs the bounds of the level and saves its map next to the log, which
+//! are used for the area and the background unless others are given
+
+use std::{
+    error::Error,
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use image::{Rgb, RgbImage, RgbaImage};
+use serde::Deserialize;
+
+const USAGE: &str = "\
+Usage: dreamseeker_heatmap [OPTIONS] <LOG>...
+
+Renders where an event happened, seen from above, over everywhere the players went.
+Each LOG is a .jsonl telemetry log or a directory of them.
+
+Options:
+  --event <NAME>    Event to heat up [default: death]
+  --out <PATH>      Image to write [default: heatmap.png]
+  --size <PIXELS>   Length of the longest side [default: 1024]
+  --radius <METERS> How far each event spreads [default: 2]
+  --bounds <MIN_X>,<MIN_Z>,<MAX_X>,<MAX_Z>
+                    Area of the level to show [default: the level in the logs,
+                    or everywhere the logs went]
+  --map <PATH>      Top-down image of the level to draw underneath, covering the
+                    level in the logs [default: the map saved with the logs]";
+
+/// Space around the logged positions when the bounds aren't given, in meters
+const MARGIN: f32 = 5.0;
+const BACKGROUND: [f32; 3] = [0.05, 0.05, 0.08];
+const TRAIL: [f32; 3] = [0.45, 0.45, 0.5];
+/// How much of the map shows through under the trail and the heat
+const MAP_OPACITY: f32 = 0.6;
+
+/// One line of a log. Only the parts needed for the heatmap
+#[derive(Deserialize)]
+struct Record {
+    event: String,
+    position: Option<[f32; 3]>,
+    bounds: Option<[f32; 4]>,
+}
+
+/// Everything read from the logs
+#[derive(Default)]
+struct Samples {
+    /// Every sampled position, on the XZ plane
+    trail: Vec<[f32; 2]>,
+    /// Where the chosen event happened
+    events: Vec<[f32; 2]>,
+    /// The bounds of the level in each log that has them, with the map saved next to it
+    levels: Vec<(Bounds, Option<PathBuf>)>,
+}
+
+struct Options {
+    logs: Vec<PathBuf>,
+    event: String,
+    out: PathBuf,
+    size: u32,
+    radius: f32,
+    bounds: Option<Bounds>,
+    map: Option<PathBuf>,
+}
+
+/// A rectangle on the XZ plane
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Bounds {
+    min: [f32; 2],
+    max: [f32; 2],
+}
+
+impl Bounds {
+    fn around(points: impl Iterator<Item = [f32; 2]>) -> Option<Self> {
+        points.fold(None, |bounds: Option<Self>, [x, z]| {
+            Some(match bounds {
+                Some(b) => Self {
+                    min: [b.min[0].min(x), b.min[1].min(z)],
+                    max: [b.max[0].max(x), b.max[1].max(z)],
+                },
+                None => Self {
+                    min: [x, z],
+                    max: [x, z],
+                },
+            })
+        })
+    }
+
+    fn grow(self, by: f32) -> Self {
+        Self {
+            min: [self.min[0] - by, self.min[1] - by],
+            max: [self.max[0] + by, self.max[1] + by],
+        }
+    }
+
+    fn size(self) -> [f32; 2] {
+        [self.max[0] - self.min[0], self.max[1] - self.min[1]]
+    }
+
+    fn union(self, other: Self) -> Self {
+        Self {
+            min: [self.min[0].min(other.min[0]), self.min[1].min(other.min[1])],
+            max: [self.max[0].max(other.max[0]), self.max[1].max(other.max[1])],
+        }
+    }
+}
+
+/// A top-down image covering `bounds`, with -Z at the top
+struct Map {
+    image: RgbaImage,
+    bounds: Bounds,
+}
+
+impl Map {
+    /// The color of the map at a point, blended over `under` by its alpha
+    fn sample(&self, [x, z]: [f32; 2], under: [f32; 3]) -> [f32; 3] {
+        let [width, height] = self.bounds.size();
+        let u = (x - self.bounds.min[0]) / width;
+        let v = (z - self.bounds.min[1]) / height;
+        if !(0.0..1.0).contains(&u) || !(0.0..1.0).contains(&v) {
+            return under;
+        }
+
+        let pixel = self.image.get_pixel(
+            (u * self.image.width() as f32) as u32,
+            (v * self.image.height() as f32) as u32,
+        );
+        let [r, g, b, a] = pixel.0.map(|c| c as f32 / 255.0);
+        mix(under, [r, g, b], a * MAP_OPACITY)
+    }
+}
+
+/// Values accumulated per pixel
+struct Grid {
+    width: u32,
+    height: u32,
+    cells: Vec<f32>,
+}
+
+impl Grid {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0.0; (width * height) as usize],
+        }
+    }
+
+    /// Adds a gaussian blob centered on `(x, y)`, in pixels
+    fn splat(&mut self, x: f32, y: f32, radius: f32) {
+        let reach = (radius * 2.0).ceil() as i64;
+        let (cx, cy) = (x.round() as i64, y.round() as i64);
+
+        for py in (cy - reach).max(0)..=(cy + reach).min(self.height as i64 - 1) {
+            for px in (cx - reach).max(0)..=(cx + reach).min(self.width as i64 - 1) {
+                let d2 = (px as f32 - x).powi(2) + (py as f32 - y).powi(2);
+                self.cells[(py as u32 * self.width + px as u32) as usize] +=
+                    (-d2 / (2.0 * radius * radius)).exp();
+            }
+        }
+    }
+
+    fn add(&mut self, x: f32, y: f32) {
+        if x >= 0.0 && y >= 0.0 && (x as u32) < self.width && (y as u32) < self.height {
+            self.cells[(y as u32 * self.width + x as u32) as usize] += 1.0;
+        }
+    }
+
+    fn max(&self) -> f32 {
+        self.cells.iter().copied().fold(0.0, f32::max)
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        logs: Vec::new(),
+        event: "death".into(),
+        out: "heatmap.png".into(),
+        size: 1024,
+        radius: 2.0,
+        bounds: None,
+        map: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+
+        match arg.as_str() {
+            "--event" => options.event = value()?,
+            "--out" => options.out = value()?.into(),
+            "--size" => options.size = value()?.parse().map_err(|e| format!("--size: {e}"))?,
+            "--radius" => {
+                options.radius = value()?.parse().map_err(|e| format!("--radius: {e}"))?
+            }
+            "--bounds" => options.bounds = Some(parse_bounds(&value()?)?),
+            "--map" => options.map = Some(value()?.into()),
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {arg}")),
+            _ => options.logs.push(arg.into()),
+        }
+    }
+
+    if options.logs.is_empty() {
+        return Err("No logs given".into());
+    }
+    if options.size == 0 || options.radius <= 0.0 {
+        return Err("--size and --radius must be positive".into());
+    }
+
+    Ok(options)
+}
+
+fn parse_bounds(text: &str) -> Result<Bounds, String> {
+    let values = text
+        .split(',')
+        .map(|v| v.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("--bounds: {e}"))?;
+
+    match values[..] {
+        [min_x, min_z, max_x, max_z] if min_x < max_x && min_z < max_z => Ok(Bounds {
+            min: [min_x, min_z],
+            max: [max_x, max_z],
+        }),
+        _ => Err("--bounds needs MIN_X,MIN_Z,MAX_X,MAX_Z with the minimums first".into()),
+    }
+}
+
+fn run(options: Options) -> Result<(), Box<dyn Error>> {
+    let mut files = Vec::new();
+    for path in &options.logs {
+        collect_logs(path, &mut files)?;
+    }
+
+    let mut samples = Samples::default();
+    for file in &files {
+        read_log(file, &options.event, &mut samples)?;
+    }
+    let Samples {
+        trail,
+        events,
+        levels,
+    } = samples;
+
+    let level = levels
+        .iter()
+        .map(|&(bounds, _)| bounds)
+        .reduce(Bounds::union);
+    let bounds = options
+        .bounds
+        .or(level)
+        .or_else(|| Bounds::around(trail.iter().chain(&events).copied()).map(|b| b.grow(MARGIN)))
+        .ok_or("The logs have no positions in them")?;
+
+    // The latest map saved with the logs, unless one is given
+    let map = match (
+        &options.map,
+        levels.iter().rev().find(|(_, map)| map.is_some()),
+    ) {
+        (Some(path), _) => Some((path.clone(), level.unwrap_or(bounds))),
+        (None, Some((level, Some(path)))) => Some((path.clone(), *level)),
+        _ => None,
+    };
+    let map = map
+        .map(|(path, bounds)| -> Result<_, Box<dyn Error>> {
+            let image = image::open(&path)
+                .map_err(|e| format!("{}: {e}", path.display()))?
+                .to_rgba8();
+            Ok(Map { image, bounds })
+        })
+        .transpose()?;
+
+    let [width, height] = bounds.size();
+    let scale = options.size as f32 / width.max(height);
+    let (w, h) = (
+        ((width * scale).round() as u32).max(1),
+        ((height * scale).round() as u32).max(1),
+    );
+    // X goes right and Z goes down, like looking down from above with -Z forward
+    let to_pixel = |[x, z]: [f32; 2]| ((x - bounds.min[0]) * scale, (z - bounds.min[1]) * scale);
+
+    let mut trail_grid = Grid::new(w, h);
+    for &point in &trail {
+        let (x, y) = to_pixel(point);
+        trail_grid.add(x, y);
+    }
+
+    let mut heat = Grid::new(w, h);
+    for &point in &events {
+        let (x, y) = to_pixel(point);
+        heat.splat(x, y, options.radius * scale);
+    }
+
+    // The world position in the middle of a pixel
+    let to_world = |x: u32, y: u32| {
+        [
+            bounds.min[0] + (x as f32 + 0.5) / scale,
+            bounds.min[1] + (y as f32 + 0.5) / scale,
+        ]
+    };
+    let background = |x, y| match &map {
+        Some(map) => map.sample(to_world(x, y), BACKGROUND),
+        None => BACKGROUND,
+    };
+
+    render(&trail_grid, &heat, background).save(&options.out)?;
+
+    eprintln!(
+        "{} {} events and {} positions from {} logs written to {}",
+        events.len(),
+        options.event,
+        trail.len(),
+        files.len(),
+        options.out.display(),
+    );
+    Ok(())
+}
+
+fn collect_logs(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    if path.is_dir() {
+        let mut entries = fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        entries.sort();
+        files.extend(
+            entries
+                .into_iter()
+                .filter(|p| p.extension().is_some_and(|e| e == "jsonl")),
+        );
+    } else {
+        files.push(path.to_owned());
+    }
+
+    Ok(())
+}
+
+/// Collects the XZ positions of every sample and of every `event`, and the bounds of the level
+fn read_log(path: &Path, event: &str, samples: &mut Samples) -> Result<(), Box<dyn Error>> {
+    let file = fs::File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        // The game may have been closed halfway through a line
+        let record = match serde_json::from_str::<Record>(&line) {
+            Ok(record) => record,
+            Err(e) => {
+                eprintln!("{}:{}: skipping line: {e}", path.display(), i + 1);
+                continue;
+            }
+        };
+
+        if record.event == "level"
+            && let Some([min_x, min_z, max_x, max_z]) = record.bounds
+        {
+            let map = path.with_extension("map.png");
+            samples.levels.push((
+                Bounds {
+                    min: [min_x, min_z],
+                    max: [max_x, max_z],
+                },
+                map.is_file().then_some(map),
+            ));
+        }
+
+        let Some([x, _, z]) = record.position else {
+            continue;
+        };
+        if record.event == "position" {
+            samples.trail.push([x, z]);
+        }
+        if record.event == event {
+            samples.events.push([x, z]);
+        }
+    }
+
+    Ok(())
+}
+
+/// Where the players went in gray over the `background`, with the heat on top going from
+/// blue to red to yellow
+fn render(trail: &Grid, heat: &Grid, background: impl Fn(u32, u32) -> [f32; 3]) -> RgbImage {
+    let trail_max = trail.max().max(1.0);
+    let heat_max = heat.max();
+
+    RgbImage::from_fn(trail.width, trail.height, |x, y| {
+        let i = (y * trail.width + x) as usize;
+
+        let visits = trail.cells[i].ln_1p() / trail_max.ln_1p();
+        let mut color = mix(background(x, y), TRAIL, visits);
+
+        if heat_max > 0.0 {
+            let t = heat.cells[i] / heat_max;
+            color = mix(color, heat_color(t), (t * 4.0).min(1.0));
+        }
+
+        Rgb(color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8))
+    })
+}
+
+fn heat_color(t: f32) -> [f32; 3] {
+    const STOPS: [[f32; 3]; 4] = [
+        [0.1, 0.2, 1.0],
+        [0.9, 0.1, 0.2],
+        [1.0, 0.8, 0.1],
+        [1.0, 1.0, 0.9],
+    ];
+
+    let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f32;
+    let i = (t.floor() as usize).min(STOPS.len() - 2);
+    mix(STOPS[i], STOPS[i + 1], t - i as f32)
+}
+
+fn mix(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [0, 1, 2].map(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bounds() {
+        let bounds = parse_bounds("-10, -5.5,20,30").unwrap();
+        assert_eq!(
+            bounds,
+            Bounds {
+                min: [-10.0, -5.5],
+                max: [20.0, 30.0]
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_bad_bounds() {
+        assert!(parse_bounds("1,2,3").is_err());
+        assert!(parse_bounds("1,2,3,4,5").is_err());
+        assert!(parse_bounds("a,2,3,4").is_err());
+        assert!(parse_bounds("").is_err());
+        // Minimums must come first
+        assert!(parse_bounds("10,0,0,10").is_err());
+        assert!(parse_bounds("0,0,0,10").is_err());
+    }
+
+    #[test]
+    fn bounds_around_points() {
+        let points = [[1.0, 5.0], [-2.0, 3.0], [4.0, -1.0]];
+        let bounds = Bounds::around(points.into_iter()).unwrap();
+
+        assert_eq!(
+            bounds,
+            Bounds {
+                min: [-2.0, -1.0],
+                max: [4.0, 5.0]
+            }
+        );
+        assert_eq!(bounds.size(), [6.0, 6.0]);
+    }
+
+    #[test]
+    fn no_bounds_without_points() {
+        assert_eq!(Bounds::around(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn grows_on_every_side() {
+        let bounds = Bounds {
+            min: [0.0, 0.0],
+            max: [2.0, 1.0],
+        }
+        .grow(1.0);
+
+        assert_eq!(bounds.min, [-1.0, -1.0]);
+        assert_eq!(bounds.size(), [4.0, 3.0]);
+    }
+
+    #[test]
+    fn reads_the_level() {
+        let dir = std::env::temp_dir().join(format!("heatmap-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let log = dir.join("1000.jsonl");
+        fs::write(
+            &log,
+            r#"{"t":0.0,"event":"session","unix_time":1000}
+{"t":0.5,"event":"level","bounds":[-10.0,-20.0,30.0,40.0]}
+{"t":1.0,"event":"position","position":[1.0,2.0,3.0]}
+{"t":2.0,"event":"death","position":[4.0,5.0,6.0]}
+{"t":2.5,"event":"pos"#,
+        )
+        .unwrap();
+
+        let mut samples = Samples::default();
+        read_log(&log, "death", &mut samples).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(samples.trail, [[1.0, 3.0]]);
+        assert_eq!(samples.events, [[4.0, 6.0]]);
+        assert_eq!(
+            samples.levels,
+            [(
+                Bounds {
+                    min: [-10.0, -20.0],
+                    max: [30.0, 40.0]
+                },
+                None
+            )]
+        );
+    }
+
+    #[test]
+    fn map_covers_its_bounds() {
+        let mut image = RgbaImage::new(2, 2);
+        image.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+        image.put_pixel(1, 1, image::Rgba([0, 0, 255, 0]));
+        let map = Map {
+            image,
+            bounds: Bounds {
+                min: [0.0, 0.0],
+                max: [4.0, 4.0],
+            },
+        };
+        let under = [0.0; 3];
+
+        // Top left is -X -Z
+        assert_eq!(map.sample([1.0, 1.0], under), [MAP_OPACITY, 0.0, 0.0]);
+        // Transparent where nothing was captured
+        assert_eq!(map.sample([3.0, 3.0], under), under);
+        assert_eq!(map.sample([-1.0, 1.0], under), under);
+        assert_eq!(map.sample([1.0, 4.0], under), under);
+    }
+}